@@ -0,0 +1,237 @@
+//! Derive macro for [egui-tetra](https://crates.io/crates/egui-tetra)'s
+//! `Inspect` trait.
+//!
+//! This crate is re-exported by egui-tetra when its `derive` feature is
+//! enabled, so it shouldn't be used directly.
+
+#![warn(missing_docs)]
+#![allow(clippy::tabs_in_doc_comments)]
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+	parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Expr, Fields, LitStr,
+};
+
+/// Settings for a field from its `#[inspect(...)]` attributes.
+#[derive(Default)]
+struct FieldAttributes {
+	range: Option<Expr>,
+	step: Option<Expr>,
+	read_only: bool,
+	label: Option<LitStr>,
+	skip: bool,
+}
+
+impl FieldAttributes {
+	fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+		let mut attributes = Self::default();
+		for attr in attrs.iter().filter(|attr| attr.path().is_ident("inspect")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("range") {
+					attributes.range = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("step") {
+					attributes.step = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("label") {
+					attributes.label = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("read_only") {
+					attributes.read_only = true;
+				} else if meta.path.is_ident("skip") {
+					attributes.skip = true;
+				} else {
+					return Err(
+						meta.error("expected `range`, `step`, `label`, `read_only`, or `skip`")
+					);
+				}
+				Ok(())
+			})?;
+		}
+		Ok(attributes)
+	}
+
+	/// Returns an expression that builds the field's `InspectOptions`,
+	/// inheriting `read_only` from the parent's options.
+	fn options(&self) -> TokenStream {
+		let read_only = self.read_only;
+		let mut options = quote! {
+			::egui_tetra::InspectOptions::new().read_only(options.is_read_only() || #read_only)
+		};
+		if let Some(range) = &self.range {
+			options = quote! {
+				#options.range({
+					let range = #range;
+					(*range.start() as f64)..=(*range.end() as f64)
+				})
+			};
+		}
+		if let Some(step) = &self.step {
+			options = quote! { #options.step((#step) as f64) };
+		}
+		options
+	}
+}
+
+/// Derives egui-tetra's `Inspect` trait.
+///
+/// Structs are shown as a grid with a row for each field, and enums
+/// without fields are shown as a combo box. Fields can be customized
+/// with the `#[inspect(...)]` attribute:
+/// - `range = <start>..=<end>`: edits numbers with a slider limited to
+///   the range
+/// - `step = <step>`: rounds numbers to multiples of the step
+/// - `read_only`: shows the field without allowing it to be edited
+/// - `label = "<label>"`: shows a different label than the field's name
+/// - `skip`: doesn't show the field
+///
+/// ```
+/// use egui_tetra::{egui, Inspect, UiInspectExt};
+/// use tetra::math::Vec2;
+///
+/// #[derive(Inspect)]
+/// enum Shape {
+/// 	Circle,
+/// 	Square,
+/// }
+///
+/// #[derive(Inspect)]
+/// struct Ball {
+/// 	position: Vec2<f32>,
+/// 	#[inspect(range = 0.0..=1000.0, step = 10.0)]
+/// 	gravity: f32,
+/// 	#[inspect(read_only)]
+/// 	bounces: u32,
+/// 	#[inspect(label = "shape")]
+/// 	kind: Shape,
+/// 	#[inspect(skip)]
+/// 	id: u64,
+/// }
+///
+/// fn ui(ui: &mut egui::Ui, ball: &mut Ball) {
+/// 	ui.inspect(ball);
+/// }
+/// ```
+#[proc_macro_derive(Inspect, attributes(inspect))]
+pub fn derive_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	match expand(input) {
+		Ok(tokens) => tokens.into(),
+		Err(error) => error.to_compile_error().into(),
+	}
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream> {
+	let name = &input.ident;
+	let body = match &input.data {
+		Data::Struct(data) => expand_struct(name, &data.fields)?,
+		Data::Enum(data) => expand_enum(name, data)?,
+		Data::Union(_) => {
+			return Err(syn::Error::new(
+				input.span(),
+				"Inspect can't be derived for unions",
+			))
+		}
+	};
+	for param in input.generics.type_params_mut() {
+		param.bounds.push(parse_quote!(::egui_tetra::Inspect));
+	}
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_generics ::egui_tetra::Inspect for #name #ty_generics #where_clause {
+			fn inspect(
+				&mut self,
+				ui: &mut ::egui_tetra::egui::Ui,
+				options: &::egui_tetra::InspectOptions,
+			) -> ::egui_tetra::egui::Response {
+				#body
+			}
+		}
+	})
+}
+
+fn expand_struct(name: &syn::Ident, fields: &Fields) -> syn::Result<TokenStream> {
+	let mut rows = vec![];
+	for (index, field) in fields.iter().enumerate() {
+		let attributes = FieldAttributes::parse(&field.attrs)?;
+		if attributes.skip {
+			continue;
+		}
+		let (member, default_label) = match &field.ident {
+			Some(ident) => (quote!(#ident), ident.to_string()),
+			None => {
+				let index = syn::Index::from(index);
+				(quote!(#index), index.index.to_string())
+			}
+		};
+		let label = match &attributes.label {
+			Some(label) => label.value(),
+			None => default_label,
+		};
+		let field_options = attributes.options();
+		rows.push(quote! {
+			changed |= ::egui_tetra::__inspect::inspect_field(
+				ui,
+				#label,
+				&mut self.#member,
+				&#field_options,
+			)
+			.changed();
+		});
+	}
+	let id_source = name.to_string();
+	Ok(quote! {
+		::egui_tetra::__inspect::inspect_fields(ui, #id_source, |ui| {
+			let mut changed = false;
+			#(#rows)*
+			changed
+		})
+	})
+}
+
+fn expand_enum(name: &syn::Ident, data: &syn::DataEnum) -> syn::Result<TokenStream> {
+	if data.variants.is_empty() {
+		return Err(syn::Error::new_spanned(
+			name,
+			"Inspect can't be derived for enums with no variants",
+		));
+	}
+	let mut variants = vec![];
+	for variant in &data.variants {
+		if !matches!(variant.fields, Fields::Unit) {
+			return Err(syn::Error::new(
+				variant.span(),
+				"Inspect can only be derived for enums whose variants have no fields",
+			));
+		}
+		variants.push(&variant.ident);
+	}
+	let labels: Vec<String> = variants.iter().map(|variant| variant.to_string()).collect();
+	let id_source = name.to_string();
+	Ok(quote! {
+		let selected = match self {
+			#(Self::#variants => #labels,)*
+		};
+		let mut changed = false;
+		let mut response = ui
+			.add_enabled_ui(!options.is_read_only(), |ui| {
+				::egui_tetra::egui::ComboBox::from_id_source(#id_source)
+					.selected_text(selected)
+					.show_ui(ui, |ui| {
+						#(
+							if ui
+								.selectable_label(matches!(self, Self::#variants), #labels)
+								.clicked()
+							{
+								*self = Self::#variants;
+								changed = true;
+							}
+						)*
+					})
+					.response
+			})
+			.inner;
+		if changed {
+			response.mark_changed();
+		}
+		response
+	})
+}
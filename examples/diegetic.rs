@@ -0,0 +1,95 @@
+use std::error::Error;
+
+use egui_tetra::{egui, EguiWrapper};
+use tetra::{
+	graphics::{
+		mesh::{Mesh, ShapeStyle},
+		Color, Rectangle,
+	},
+	math::{Mat4, Vec2, Vec3},
+	Context, Event,
+};
+
+const TERMINAL_WIDTH: f32 = 240.0;
+const TERMINAL_HEIGHT: f32 = 160.0;
+
+/// Uses a separate [`EguiWrapper`] for a terminal screen drawn
+/// inside the game world, alongside a normal screen-space GUI.
+struct MainState {
+	screen_egui: EguiWrapper,
+	terminal_egui: EguiWrapper,
+	terminal_frame: Mesh,
+	terminal_rotation: f32,
+	terminal_text: String,
+}
+
+impl MainState {
+	fn new(ctx: &mut Context) -> Result<Self, Box<dyn Error>> {
+		let mut terminal_egui = EguiWrapper::new();
+		terminal_egui.set_screen_size(Some(Vec2::new(TERMINAL_WIDTH, TERMINAL_HEIGHT)));
+		Ok(Self {
+			screen_egui: EguiWrapper::new(),
+			terminal_egui,
+			terminal_frame: Mesh::rectangle(
+				ctx,
+				ShapeStyle::Fill,
+				Rectangle::new(-8.0, -8.0, TERMINAL_WIDTH + 16.0, TERMINAL_HEIGHT + 16.0),
+			)?,
+			terminal_rotation: 0.1,
+			terminal_text: String::from("> "),
+		})
+	}
+
+	fn terminal_transform(&self) -> Mat4<f32> {
+		Mat4::<f32>::translation_3d(Vec3::new(400.0, 300.0, 0.0))
+			* Mat4::rotation_z(self.terminal_rotation)
+			* Mat4::translation_2d(Vec2::new(-TERMINAL_WIDTH, -TERMINAL_HEIGHT) / 2.0)
+	}
+}
+
+impl tetra::State<Box<dyn Error>> for MainState {
+	fn update(&mut self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+		self.terminal_egui
+			.set_transform(Some(self.terminal_transform()));
+
+		self.screen_egui.begin_frame(ctx)?;
+		let screen_egui_ctx = self.screen_egui.ctx().clone();
+		egui::Window::new("Settings").show(&screen_egui_ctx, |ui| {
+			ui.label("Terminal rotation");
+			ui.add(egui::Slider::new(&mut self.terminal_rotation, -1.0..=1.0));
+		});
+		self.screen_egui.end_frame(ctx)?;
+
+		self.terminal_egui.begin_frame(ctx)?;
+		let terminal_egui_ctx = self.terminal_egui.ctx().clone();
+		egui::CentralPanel::default().show(&terminal_egui_ctx, |ui| {
+			ui.heading("Terminal");
+			ui.text_edit_multiline(&mut self.terminal_text);
+		});
+		self.terminal_egui.end_frame(ctx)?;
+		Ok(())
+	}
+
+	fn draw(&mut self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+		tetra::graphics::clear(ctx, Color::rgb(0.1, 0.1, 0.15));
+		tetra::graphics::set_transform_matrix(ctx, self.terminal_transform());
+		self.terminal_frame.draw(ctx, Vec2::zero());
+		tetra::graphics::reset_transform_matrix(ctx);
+		self.terminal_egui.draw_frame(ctx);
+		self.screen_egui.draw_frame(ctx);
+		Ok(())
+	}
+
+	fn event(&mut self, ctx: &mut Context, event: Event) -> Result<(), Box<dyn Error>> {
+		self.screen_egui.event(ctx, &event)?;
+		self.terminal_egui.event(ctx, &event)?;
+		Ok(())
+	}
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+	tetra::ContextBuilder::new("Diegetic UI example", 800, 600)
+		.show_mouse(true)
+		.build()?
+		.run(MainState::new)
+}
@@ -0,0 +1,80 @@
+use std::error::Error;
+
+use egui_tetra::{egui, State, StateWrapper};
+use tetra::{
+	graphics::{
+		mesh::{Mesh, ShapeStyle},
+		Canvas, Color,
+	},
+	math::Vec2,
+	Context, Event,
+};
+
+const GAME_WIDTH: i32 = 320;
+const GAME_HEIGHT: i32 = 240;
+
+struct MainState {
+	cursor_position: Vec2<f32>,
+	circle_radius: f32,
+	circle_mesh: Mesh,
+}
+
+impl MainState {
+	fn new(ctx: &mut Context) -> Result<Self, Box<dyn Error>> {
+		Ok(Self {
+			cursor_position: Vec2::zero(),
+			circle_radius: 16.0,
+			circle_mesh: Mesh::circle(ctx, ShapeStyle::Fill, Vec2::zero(), 1.0)?,
+		})
+	}
+}
+
+impl State<Box<dyn Error>> for MainState {
+	fn ui(&mut self, _ctx: &mut Context, egui_ctx: &egui::CtxRef) -> Result<(), Box<dyn Error>> {
+		egui::SidePanel::left("tools").show(egui_ctx, |ui| {
+			ui.heading("Tools");
+			ui.label(format!(
+				"Cursor: {:.0}, {:.0}",
+				self.cursor_position.x, self.cursor_position.y
+			));
+			ui.label("Circle radius");
+			ui.add(egui::Slider::new(&mut self.circle_radius, 1.0..=64.0));
+		});
+		Ok(())
+	}
+
+	fn draw(&mut self, ctx: &mut Context, _egui_ctx: &egui::CtxRef) -> Result<(), Box<dyn Error>> {
+		tetra::graphics::clear(ctx, Color::rgb(0.2, 0.3, 0.4));
+		self.circle_mesh.draw(
+			ctx,
+			tetra::graphics::DrawParams::new()
+				.position(self.cursor_position)
+				.scale(Vec2::broadcast(self.circle_radius)),
+		);
+		Ok(())
+	}
+
+	fn event(
+		&mut self,
+		_ctx: &mut Context,
+		_egui_ctx: &egui::CtxRef,
+		event: Event,
+	) -> Result<(), Box<dyn Error>> {
+		// positions in events are already converted to game coordinates
+		if let Event::MouseMoved { position, .. } = event {
+			self.cursor_position = position;
+		}
+		Ok(())
+	}
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+	tetra::ContextBuilder::new("Editor example", 1280, 720)
+		.show_mouse(true)
+		.resizable(true)
+		.build()?
+		.run(|ctx| {
+			let canvas = Canvas::new(ctx, GAME_WIDTH, GAME_HEIGHT)?;
+			Ok(StateWrapper::new(MainState::new(ctx)?).with_viewport(canvas))
+		})
+}
@@ -0,0 +1,125 @@
+use std::error::Error;
+
+use egui_tetra::{egui, Scene, SceneStack, StateWrapper, Transition};
+use tetra::{
+	graphics::{
+		mesh::{Mesh, ShapeStyle},
+		Color,
+	},
+	input::Key,
+	math::Vec2,
+	Context, Event,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+struct MenuScene;
+
+impl Scene<Box<dyn Error>> for MenuScene {
+	fn ui(
+		&mut self,
+		_ctx: &mut Context,
+		egui_ctx: &egui::CtxRef,
+	) -> Result<Transition<Box<dyn Error>>> {
+		let mut transition = Transition::None;
+		egui::CentralPanel::default().show(egui_ctx, |ui| {
+			ui.heading("Scenes example");
+			if ui.button("Play").clicked() {
+				transition = Transition::Push(Box::new(GameplayScene::new()));
+			}
+			if ui.button("Quit").clicked() {
+				transition = Transition::Pop;
+			}
+		});
+		Ok(transition)
+	}
+}
+
+struct GameplayScene {
+	time: f32,
+	circle_mesh: Option<Mesh>,
+}
+
+impl GameplayScene {
+	fn new() -> Self {
+		Self {
+			time: 0.0,
+			circle_mesh: None,
+		}
+	}
+}
+
+impl Scene<Box<dyn Error>> for GameplayScene {
+	fn update(
+		&mut self,
+		ctx: &mut Context,
+		_egui_ctx: &egui::CtxRef,
+	) -> Result<Transition<Box<dyn Error>>> {
+		self.time += tetra::time::get_delta_time(ctx).as_secs_f32();
+		Ok(Transition::None)
+	}
+
+	fn draw(&mut self, ctx: &mut Context, _egui_ctx: &egui::CtxRef) -> Result<()> {
+		tetra::graphics::clear(ctx, Color::BLACK);
+		if self.circle_mesh.is_none() {
+			self.circle_mesh = Some(Mesh::circle(ctx, ShapeStyle::Fill, Vec2::zero(), 32.0)?);
+		}
+		if let Some(circle_mesh) = &self.circle_mesh {
+			circle_mesh.draw(ctx, Vec2::new(400.0 + self.time.sin() * 200.0, 300.0));
+		}
+		Ok(())
+	}
+
+	fn event(
+		&mut self,
+		_ctx: &mut Context,
+		_egui_ctx: &egui::CtxRef,
+		event: Event,
+	) -> Result<(Transition<Box<dyn Error>>, bool)> {
+		if let Event::KeyPressed { key: Key::Escape } = event {
+			return Ok((Transition::Push(Box::new(PauseScene)), true));
+		}
+		Ok((Transition::None, false))
+	}
+}
+
+struct PauseScene;
+
+impl Scene<Box<dyn Error>> for PauseScene {
+	fn ui(
+		&mut self,
+		_ctx: &mut Context,
+		egui_ctx: &egui::CtxRef,
+	) -> Result<Transition<Box<dyn Error>>> {
+		let mut transition = Transition::None;
+		egui::Window::new("Paused").show(egui_ctx, |ui| {
+			if ui.button("Resume").clicked() {
+				transition = Transition::Pop;
+			}
+		});
+		Ok(transition)
+	}
+
+	fn event(
+		&mut self,
+		_ctx: &mut Context,
+		_egui_ctx: &egui::CtxRef,
+		event: Event,
+	) -> Result<(Transition<Box<dyn Error>>, bool)> {
+		if let Event::KeyPressed { key: Key::Escape } = event {
+			return Ok((Transition::Pop, true));
+		}
+		Ok((Transition::None, false))
+	}
+
+	fn draw_below(&self) -> bool {
+		true
+	}
+}
+
+fn main() -> Result<()> {
+	tetra::ContextBuilder::new("Scenes example", 800, 600)
+		.show_mouse(true)
+		.build()?
+		.run(|_| Ok(StateWrapper::new(SceneStack::new(MenuScene))))
+}
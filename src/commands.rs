@@ -0,0 +1,526 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+/// The type of value a [`Command`] argument accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+	/// A whole number, like `-3` or `42`.
+	Int,
+	/// A number, like `0.5` or `10`.
+	Float,
+	/// `true` or `false`. `on`/`off`, `yes`/`no`, and `1`/`0` are
+	/// also accepted.
+	Bool,
+	/// Any text. Text with spaces can be wrapped in double quotes.
+	String,
+}
+
+impl ArgType {
+	fn parse(self, text: &str) -> Option<ArgValue> {
+		Some(match self {
+			Self::Int => ArgValue::Int(text.parse().ok()?),
+			Self::Float => ArgValue::Float(text.parse().ok()?),
+			Self::Bool => ArgValue::Bool(match text.to_lowercase().as_str() {
+				"true" | "on" | "yes" | "1" => true,
+				"false" | "off" | "no" | "0" => false,
+				_ => return None,
+			}),
+			Self::String => ArgValue::String(text.to_string()),
+		})
+	}
+}
+
+impl Display for ArgType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::Int => "int",
+			Self::Float => "float",
+			Self::Bool => "bool",
+			Self::String => "string",
+		})
+	}
+}
+
+/// The value of a [`Command`] argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+	/// The value of an [`ArgType::Int`] argument.
+	Int(i64),
+	/// The value of an [`ArgType::Float`] argument.
+	Float(f64),
+	/// The value of an [`ArgType::Bool`] argument.
+	Bool(bool),
+	/// The value of an [`ArgType::String`] argument.
+	String(String),
+}
+
+impl ArgValue {
+	/// Returns the value if it's an [`ArgValue::Int`].
+	pub fn as_int(&self) -> Option<i64> {
+		match self {
+			Self::Int(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// Returns the value if it's an [`ArgValue::Float`] or an
+	/// [`ArgValue::Int`].
+	pub fn as_float(&self) -> Option<f64> {
+		match self {
+			Self::Float(value) => Some(*value),
+			Self::Int(value) => Some(*value as f64),
+			_ => None,
+		}
+	}
+
+	/// Returns the value if it's an [`ArgValue::Bool`].
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::Bool(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// Returns the value if it's an [`ArgValue::String`].
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::String(value) => Some(value),
+			_ => None,
+		}
+	}
+}
+
+/// An argument of a [`Command`].
+#[derive(Debug, Clone)]
+struct Param {
+	name: String,
+	ty: ArgType,
+	required: bool,
+}
+
+/// The name, help text, and arguments of a command that can be
+/// registered with a [`CommandRegistry`].
+#[derive(Debug, Clone)]
+pub struct Command {
+	name: String,
+	help: String,
+	params: Vec<Param>,
+}
+
+impl Command {
+	/// Creates a new [`Command`] with no arguments.
+	///
+	/// The name is what's typed to run the command, so it shouldn't
+	/// contain spaces.
+	pub fn new(name: impl Into<String>, help: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			help: help.into(),
+			params: vec![],
+		}
+	}
+
+	/// Adds a required argument and returns the command.
+	///
+	/// Arguments added after an [optional argument](Command::optional_arg)
+	/// are also optional.
+	pub fn arg(mut self, name: impl Into<String>, ty: ArgType) -> Self {
+		let required = self.params.iter().all(|param| param.required);
+		self.params.push(Param {
+			name: name.into(),
+			ty,
+			required,
+		});
+		self
+	}
+
+	/// Adds an argument that can be left out and returns the command.
+	pub fn optional_arg(mut self, name: impl Into<String>, ty: ArgType) -> Self {
+		self.params.push(Param {
+			name: name.into(),
+			ty,
+			required: false,
+		});
+		self
+	}
+
+	/// Returns the name of the command.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the help text of the command.
+	pub fn help(&self) -> &str {
+		&self.help
+	}
+
+	/// Returns a summary of how to use the command, like
+	/// `teleport <x: float> <y: float> [instant: bool]`.
+	pub fn usage(&self) -> String {
+		let mut usage = self.name.clone();
+		for param in &self.params {
+			usage.push_str(&if param.required {
+				format!(" <{}: {}>", param.name, param.ty)
+			} else {
+				format!(" [{}: {}]", param.name, param.ty)
+			});
+		}
+		usage
+	}
+
+	fn parse_args(&self, words: &[String]) -> Result<CommandArgs, String> {
+		let required = self.params.iter().filter(|param| param.required).count();
+		if words.len() < required || words.len() > self.params.len() {
+			return Err(format!("usage: {}", self.usage()));
+		}
+		let mut values = vec![];
+		for (param, word) in self.params.iter().zip(words) {
+			match param.ty.parse(word) {
+				Some(value) => values.push((param.name.clone(), value)),
+				None => {
+					return Err(format!(
+						"expected {} for `{}`, got {:?}",
+						param.ty, param.name, word
+					))
+				}
+			}
+		}
+		Ok(CommandArgs { values })
+	}
+}
+
+/// The arguments a [`Command`] was run with.
+///
+/// Required arguments are always present. Optional arguments are
+/// present if they were given.
+#[derive(Debug, Clone, Default)]
+pub struct CommandArgs {
+	values: Vec<(String, ArgValue)>,
+}
+
+impl CommandArgs {
+	/// Returns the value of an argument, or `None` if it wasn't given.
+	pub fn get(&self, name: &str) -> Option<&ArgValue> {
+		self.values
+			.iter()
+			.find(|(param, _)| param == name)
+			.map(|(_, value)| value)
+	}
+
+	/// Returns the value of an [`ArgType::Int`] argument.
+	pub fn int(&self, name: &str) -> Option<i64> {
+		self.get(name).and_then(ArgValue::as_int)
+	}
+
+	/// Returns the value of an [`ArgType::Float`] argument.
+	pub fn float(&self, name: &str) -> Option<f64> {
+		self.get(name).and_then(ArgValue::as_float)
+	}
+
+	/// Returns the value of an [`ArgType::Bool`] argument.
+	pub fn bool(&self, name: &str) -> Option<bool> {
+		self.get(name).and_then(ArgValue::as_bool)
+	}
+
+	/// Returns the value of an [`ArgType::String`] argument.
+	pub fn str(&self, name: &str) -> Option<&str> {
+		self.get(name).and_then(ArgValue::as_str)
+	}
+
+	/// Returns the number of arguments that were given.
+	pub fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Returns `true` if no arguments were given.
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+}
+
+type CommandHandler =
+	Box<dyn FnMut(&mut tetra::Context, &egui::CtxRef, &CommandArgs) -> Result<String, String>>;
+
+struct RegisteredCommand {
+	command: Command,
+	handler: CommandHandler,
+}
+
+/// A set of named commands that can be run from text, such as
+/// the commands typed into a [`ConsolePanel`](crate::ConsolePanel).
+///
+/// Each command has a handler that receives the Tetra context, the egui
+/// context, and the parsed arguments. A handler returns text to show to
+/// the user (which can be empty), or an error message if the command
+/// failed.
+///
+/// ```
+/// use egui_tetra::{ArgType, Command, CommandRegistry, TimeControls};
+///
+/// let mut commands = CommandRegistry::new();
+/// commands.register(
+/// 	Command::new("timescale", "Sets the speed of gameplay.").arg("scale", ArgType::Float),
+/// 	|_ctx, egui_ctx, args| {
+/// 		let scale = args.float("scale").unwrap_or(1.0) as f32;
+/// 		TimeControls::edit(egui_ctx, |controls| controls.set_time_scale(scale));
+/// 		Ok(format!("time scale set to {}", scale))
+/// 	},
+/// );
+/// ```
+///
+/// Handlers don't have access to the game's [`State`](crate::State), so
+/// commands that change the game usually share data with it through an
+/// `Rc<RefCell<T>>` or egui's [memory](egui::Memory).
+#[derive(Default)]
+pub struct CommandRegistry {
+	commands: BTreeMap<String, RegisteredCommand>,
+}
+
+impl CommandRegistry {
+	/// Creates a new, empty [`CommandRegistry`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers a command, replacing any command with the same name.
+	pub fn register(
+		&mut self,
+		command: Command,
+		handler: impl FnMut(&mut tetra::Context, &egui::CtxRef, &CommandArgs) -> Result<String, String>
+			+ 'static,
+	) {
+		self.commands.insert(
+			command.name.clone(),
+			RegisteredCommand {
+				command,
+				handler: Box::new(handler),
+			},
+		);
+	}
+
+	/// Removes a command. Returns `true` if the command was registered.
+	pub fn unregister(&mut self, name: &str) -> bool {
+		self.commands.remove(name).is_some()
+	}
+
+	/// Returns the command with the given name, if there is one.
+	pub fn get(&self, name: &str) -> Option<&Command> {
+		self.commands
+			.get(name)
+			.map(|registered| &registered.command)
+	}
+
+	/// Returns the registered commands, sorted by name.
+	pub fn commands(&self) -> impl Iterator<Item = &Command> {
+		self.commands.values().map(|registered| &registered.command)
+	}
+
+	/// Returns the names of the commands that start with `prefix`,
+	/// sorted by name.
+	pub fn complete(&self, prefix: &str) -> Vec<&str> {
+		self.commands
+			.keys()
+			.filter(|name| name.starts_with(prefix))
+			.map(String::as_str)
+			.collect()
+	}
+
+	/// Parses a line of text and runs the command it names.
+	///
+	/// Words are separated by spaces, and double quotes can be used to
+	/// pass text with spaces as a single argument. Running an empty line
+	/// does nothing.
+	pub fn run(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		line: &str,
+	) -> Result<String, String> {
+		match self.parse(line)? {
+			Some((registered, args)) => (registered.handler)(ctx, egui_ctx, &args),
+			None => Ok(String::new()),
+		}
+	}
+
+	/// Finds the command a line of text names and parses its arguments.
+	/// Returns `None` if the line is empty.
+	fn parse(
+		&mut self,
+		line: &str,
+	) -> Result<Option<(&mut RegisteredCommand, CommandArgs)>, String> {
+		let words = split_words(line)?;
+		let (name, words) = match words.split_first() {
+			Some(split) => split,
+			None => return Ok(None),
+		};
+		let registered = self
+			.commands
+			.get_mut(name.as_str())
+			.ok_or_else(|| format!("unknown command `{}`", name))?;
+		let args = registered.command.parse_args(words)?;
+		Ok(Some((registered, args)))
+	}
+}
+
+/// Splits a line into words, treating text in double quotes
+/// as a single word.
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+	let mut words = vec![];
+	let mut word: Option<String> = None;
+	let mut quoted = false;
+	for c in line.chars() {
+		match c {
+			'"' => {
+				quoted = !quoted;
+				word.get_or_insert_with(String::new);
+			}
+			c if c.is_whitespace() && !quoted => words.extend(word.take()),
+			c => word.get_or_insert_with(String::new).push(c),
+		}
+	}
+	if quoted {
+		return Err("missing closing quote".to_string());
+	}
+	words.extend(word);
+	Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn words(line: &str) -> Vec<String> {
+		split_words(line).unwrap()
+	}
+
+	fn teleport() -> Command {
+		Command::new("teleport", "Moves the player.")
+			.arg("x", ArgType::Float)
+			.arg("y", ArgType::Float)
+			.optional_arg("instant", ArgType::Bool)
+	}
+
+	fn args(command: &Command, line: &str) -> Result<CommandArgs, String> {
+		command.parse_args(&words(line))
+	}
+
+	/// Creates a registry with commands that do nothing.
+	fn registry(names: &[&str]) -> CommandRegistry {
+		let mut registry = CommandRegistry::new();
+		for name in names {
+			registry.register(Command::new(*name, ""), |_, _, _| Ok(String::new()));
+		}
+		registry.register(teleport(), |_, _, _| Ok(String::new()));
+		registry
+	}
+
+	fn parse_error(registry: &mut CommandRegistry, line: &str) -> String {
+		match registry.parse(line) {
+			Ok(_) => panic!("{:?} should fail to parse", line),
+			Err(error) => error,
+		}
+	}
+
+	#[test]
+	fn splits_on_whitespace() {
+		assert_eq!(words("  spawn  enemy\t3 "), ["spawn", "enemy", "3"]);
+		assert!(words("").is_empty());
+		assert!(words("   ").is_empty());
+	}
+
+	#[test]
+	fn quoted_words() {
+		assert_eq!(words(r#"say "hello world""#), ["say", "hello world"]);
+		assert_eq!(words(r#"say pre"fix suf"fix"#), ["say", "prefix suffix"]);
+		assert_eq!(words(r#"say "" end"#), ["say", "", "end"]);
+	}
+
+	#[test]
+	fn unterminated_quote() {
+		assert_eq!(
+			split_words(r#"say "hello world"#),
+			Err("missing closing quote".to_string())
+		);
+	}
+
+	#[test]
+	fn parses_arguments() {
+		let command = teleport();
+		let parsed = args(&command, "1.5 -2").unwrap();
+		assert_eq!(parsed.len(), 2);
+		assert_eq!(parsed.float("x"), Some(1.5));
+		assert_eq!(parsed.float("y"), Some(-2.0));
+		assert_eq!(parsed.bool("instant"), None);
+		let parsed = args(&command, "0 0 yes").unwrap();
+		assert_eq!(parsed.bool("instant"), Some(true));
+	}
+
+	#[test]
+	fn wrong_argument_count() {
+		let command = teleport();
+		let usage = "usage: teleport <x: float> <y: float> [instant: bool]".to_string();
+		assert_eq!(args(&command, "1").unwrap_err(), usage);
+		assert_eq!(args(&command, "1 2 true extra").unwrap_err(), usage);
+	}
+
+	#[test]
+	fn wrong_argument_type() {
+		let command = teleport();
+		assert_eq!(
+			args(&command, "1 up").unwrap_err(),
+			r#"expected float for `y`, got "up""#
+		);
+		assert_eq!(
+			args(&command, "1 2 maybe").unwrap_err(),
+			r#"expected bool for `instant`, got "maybe""#
+		);
+		let command = Command::new("spawn", "").arg("count", ArgType::Int);
+		assert_eq!(
+			args(&command, "1.5").unwrap_err(),
+			r#"expected int for `count`, got "1.5""#
+		);
+	}
+
+	#[test]
+	fn unknown_command() {
+		let mut registry = registry(&["spawn"]);
+		assert_eq!(
+			parse_error(&mut registry, "spwan 3"),
+			"unknown command `spwan`"
+		);
+		assert!(registry.unregister("spawn"));
+		assert_eq!(
+			parse_error(&mut registry, "spawn"),
+			"unknown command `spawn`"
+		);
+	}
+
+	#[test]
+	fn parses_lines() {
+		let mut registry = registry(&[]);
+		assert!(registry.parse("").unwrap().is_none());
+		let (registered, args) = registry.parse("teleport 1 2").unwrap().unwrap();
+		assert_eq!(registered.command.name(), "teleport");
+		assert_eq!(args.float("x"), Some(1.0));
+		assert_eq!(
+			parse_error(&mut registry, r#"teleport "1 2"#),
+			"missing closing quote"
+		);
+		assert_eq!(
+			parse_error(&mut registry, "teleport 1"),
+			"usage: teleport <x: float> <y: float> [instant: bool]"
+		);
+	}
+
+	#[test]
+	fn completes_prefixes() {
+		let registry = registry(&["spawn", "speed", "quit"]);
+		assert_eq!(registry.complete("sp"), ["spawn", "speed"]);
+		assert_eq!(registry.complete("spe"), ["speed"]);
+		assert_eq!(registry.complete("q"), ["quit"]);
+		assert!(registry.complete("x").is_empty());
+		assert_eq!(
+			registry.complete(""),
+			["quit", "spawn", "speed", "teleport"]
+		);
+	}
+}
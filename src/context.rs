@@ -0,0 +1,204 @@
+use tetra::{
+	input::Key,
+	math::{Mat4, Vec2, Vec3},
+};
+
+use crate::EguiWrapper;
+
+/// The name of the egui context [`StateWrapper`](crate::StateWrapper)
+/// passes to the main [`State`](crate::State) callbacks.
+pub const MAIN_CONTEXT: &str = "main";
+
+/// An egui context managed by a [`StateWrapper`](crate::StateWrapper),
+/// along with settings for how it's shown and how it receives input.
+///
+/// Each context has its own style, scale, and memory, so for example,
+/// developer tools can be kept separate from the player-facing GUI:
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # struct MainState;
+/// # impl egui_tetra::State<Box<dyn Error>> for MainState {}
+/// use egui_tetra::{NamedContext, StateWrapper};
+/// use tetra::input::Key;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// 	tetra::ContextBuilder::new("example", 800, 600)
+/// 		.build()?
+/// 		.run(|_| {
+/// 			Ok(StateWrapper::new(MainState).with_context(
+/// 				NamedContext::new("debug")
+/// 					.priority(10)
+/// 					.toggle_key(Key::F1)
+/// 					.scale(0.75),
+/// 			))
+/// 		})
+/// }
+/// ```
+///
+/// GUIs for additional contexts are constructed in the
+/// [`State::context_ui`](crate::State::context_ui) callback or by plugins added with
+/// [`StateWrapper::add_plugin_to_context`](crate::StateWrapper::add_plugin_to_context).
+pub struct NamedContext {
+	pub(crate) name: String,
+	pub(crate) egui: EguiWrapper,
+	pub(crate) priority: i32,
+	pub(crate) visible: bool,
+	pub(crate) toggle_key: Option<Key>,
+	scale: f32,
+	/// The screen size and transform the scale was last applied with.
+	scaled: Option<(Vec2<f32>, Mat4<f32>)>,
+	#[cfg(feature = "persistence")]
+	persistence_path: Option<std::path::PathBuf>,
+	#[cfg(feature = "persistence")]
+	memory_loaded: bool,
+}
+
+impl NamedContext {
+	/// Creates a new [`NamedContext`] with a fresh egui context.
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			egui: EguiWrapper::new(),
+			priority: 0,
+			visible: true,
+			toggle_key: None,
+			scale: 1.0,
+			scaled: None,
+			#[cfg(feature = "persistence")]
+			persistence_path: None,
+			#[cfg(feature = "persistence")]
+			memory_loaded: false,
+		}
+	}
+
+	/// Sets the priority of the context and returns the context.
+	///
+	/// Contexts with a higher priority receive input before, and are
+	/// drawn on top of, contexts with a lower priority. Once a context
+	/// uses a mouse press or key press, contexts with a lower priority
+	/// won't receive it. The main context has a priority of `0`.
+	pub fn priority(mut self, priority: i32) -> Self {
+		self.priority = priority;
+		self
+	}
+
+	/// Sets a key that shows and hides the context and returns the context.
+	///
+	/// Presses of this key are not passed on to the GUI or the game.
+	pub fn toggle_key(mut self, key: Key) -> Self {
+		self.toggle_key = Some(key);
+		self
+	}
+
+	/// Sets whether the context is initially visible and returns the context.
+	///
+	/// Hidden contexts don't construct a GUI, aren't drawn, and don't
+	/// receive input.
+	pub fn visible(mut self, visible: bool) -> Self {
+		self.visible = visible;
+		self
+	}
+
+	/// Sets the style of the context and returns the context.
+	pub fn style(self, style: egui::Style) -> Self {
+		self.egui.ctx().set_style(style);
+		self
+	}
+
+	/// Sets how large the GUI is drawn relative to the window and
+	/// returns the context.
+	///
+	/// The scale is applied by setting the [transform](EguiWrapper::set_transform)
+	/// and [screen size](EguiWrapper::set_screen_size) of the context's
+	/// [`EguiWrapper`] each frame. If you set either of those yourself,
+	/// your settings win and the scale is ignored until both are set
+	/// back to `None`.
+	pub fn scale(mut self, scale: f32) -> Self {
+		self.scale = scale;
+		self
+	}
+
+	/// Sets a file to load the context's memory (such as window positions)
+	/// from and save it to, and returns the context.
+	///
+	/// The memory is loaded before the first frame and saved when the
+	/// [`StateWrapper`](crate::StateWrapper) is dropped.
+	#[cfg(feature = "persistence")]
+	pub fn persistence_path(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+		self.persistence_path = Some(path.into());
+		self
+	}
+
+	/// Returns the name of the context.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the context's [`EguiWrapper`].
+	pub fn egui(&self) -> &EguiWrapper {
+		&self.egui
+	}
+
+	/// Returns a mutable reference to the context's [`EguiWrapper`].
+	pub fn egui_mut(&mut self) -> &mut EguiWrapper {
+		&mut self.egui
+	}
+
+	/// Returns `true` if the context is currently shown.
+	pub fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	/// Shows or hides the context.
+	pub fn set_visible(&mut self, visible: bool) {
+		self.visible = visible;
+	}
+
+	/// Prepares the context for a new frame, applying the scale and
+	/// loading the context's memory if it hasn't been loaded yet.
+	pub(crate) fn prepare_frame(&mut self, ctx: &tetra::Context) -> Result<(), crate::Error> {
+		#[cfg(feature = "persistence")]
+		if !self.memory_loaded {
+			self.memory_loaded = true;
+			if let Some(path) = &self.persistence_path {
+				if path.exists() {
+					self.egui.load_memory(path)?;
+				}
+			}
+		}
+		// if the screen size or transform isn't what the scale set it
+		// to last frame, the user has set their own
+		let user_set = match self.scaled {
+			Some((screen_size, transform)) => {
+				self.egui.screen_size() != Some(screen_size)
+					|| self.egui.transform() != Some(transform)
+			}
+			None => self.egui.screen_size().is_some() || self.egui.transform().is_some(),
+		};
+		if user_set {
+			self.scaled = None;
+		} else if (self.scale - 1.0).abs() > f32::EPSILON {
+			let window_size = Vec2::new(
+				tetra::window::get_width(ctx) as f32,
+				tetra::window::get_height(ctx) as f32,
+			);
+			let screen_size = window_size / self.scale;
+			let transform = Mat4::scaling_3d(Vec3::new(self.scale, self.scale, 1.0));
+			self.egui.set_screen_size(Some(screen_size));
+			self.egui.set_transform(Some(transform));
+			self.egui.ctx().set_pixels_per_point(self.scale);
+			self.scaled = Some((screen_size, transform));
+		}
+		Ok(())
+	}
+
+	/// Saves the context's memory to its persistence file, if it has one.
+	#[cfg(feature = "persistence")]
+	pub(crate) fn save_memory(&self) -> Result<(), crate::Error> {
+		match &self.persistence_path {
+			Some(path) => self.egui.save_memory(path),
+			None => Ok(()),
+		}
+	}
+}
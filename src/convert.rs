@@ -0,0 +1,352 @@
+//! Conversions between egui and Tetra types.
+//!
+//! Rust doesn't allow implementing [`From`] for a type from one crate
+//! in terms of a type from another, so these conversions use their own
+//! traits that mirror [`From`] and [`Into`]:
+//! - [`FromTetra`] and [`IntoEgui`] convert Tetra types to egui types.
+//! - [`FromEgui`] and [`IntoTetra`] convert egui types to Tetra types.
+//!
+//! Like [`Into`], [`IntoEgui`] and [`IntoTetra`] are implemented for
+//! every type that can be converted, so only the `From` traits need
+//! to be implemented.
+//!
+//! ```
+//! use egui_tetra::convert::{FromEgui, IntoEgui};
+//! use egui_tetra::egui;
+//! use tetra::math::Vec2;
+//!
+//! let position: egui::Pos2 = Vec2::new(16.0, 32.0).into_egui();
+//! assert_eq!(position, egui::pos2(16.0, 32.0));
+//! assert_eq!(Vec2::from_egui(position), Vec2::new(16.0, 32.0));
+//! ```
+//!
+//! Supported conversions:
+//!
+//! | Tetra | egui |
+//! |-|-|
+//! | [`Vec2<f32>`] | [`egui::Pos2`], [`egui::Vec2`] |
+//! | [`Rectangle<f32>`], [`Rectangle<i32>`] | [`egui::Rect`] |
+//! | [`Color`] | [`egui::Color32`], [`egui::Rgba`] |
+//! | [`Key`] | [`egui::Key`] |
+//! | [`MouseButton`] | [`egui::PointerButton`] |
+//!
+//! egui doesn't have an equivalent for every Tetra key and mouse button,
+//! so those convert to an [`Option`].
+
+use egui::color::{gamma_from_linear, linear_from_gamma};
+use tetra::{
+	graphics::{Color, Rectangle},
+	input::{Key, MouseButton},
+	math::Vec2,
+};
+
+/// Creates an egui value from a Tetra value.
+pub trait FromTetra<T> {
+	/// Converts the Tetra value to this type.
+	fn from_tetra(value: T) -> Self;
+}
+
+/// Converts a Tetra value to an egui value.
+///
+/// This is implemented for every type `T` where the egui type
+/// implements [`FromTetra<T>`].
+pub trait IntoEgui<T> {
+	/// Converts this value to the egui type.
+	fn into_egui(self) -> T;
+}
+
+impl<T, U: FromTetra<T>> IntoEgui<U> for T {
+	fn into_egui(self) -> U {
+		U::from_tetra(self)
+	}
+}
+
+/// Creates a Tetra value from an egui value.
+pub trait FromEgui<T> {
+	/// Converts the egui value to this type.
+	fn from_egui(value: T) -> Self;
+}
+
+/// Converts an egui value to a Tetra value.
+///
+/// This is implemented for every type `T` where the Tetra type
+/// implements [`FromEgui<T>`].
+pub trait IntoTetra<T> {
+	/// Converts this value to the Tetra type.
+	fn into_tetra(self) -> T;
+}
+
+impl<T, U: FromEgui<T>> IntoTetra<U> for T {
+	fn into_tetra(self) -> U {
+		U::from_egui(self)
+	}
+}
+
+impl FromTetra<Vec2<f32>> for egui::Pos2 {
+	fn from_tetra(value: Vec2<f32>) -> Self {
+		egui::pos2(value.x, value.y)
+	}
+}
+
+impl FromTetra<Vec2<f32>> for egui::Vec2 {
+	fn from_tetra(value: Vec2<f32>) -> Self {
+		egui::vec2(value.x, value.y)
+	}
+}
+
+impl FromEgui<egui::Pos2> for Vec2<f32> {
+	fn from_egui(value: egui::Pos2) -> Self {
+		Vec2::new(value.x, value.y)
+	}
+}
+
+impl FromEgui<egui::Vec2> for Vec2<f32> {
+	fn from_egui(value: egui::Vec2) -> Self {
+		Vec2::new(value.x, value.y)
+	}
+}
+
+impl FromTetra<Rectangle<f32>> for egui::Rect {
+	fn from_tetra(value: Rectangle<f32>) -> Self {
+		egui::Rect::from_min_size(
+			egui::pos2(value.x, value.y),
+			egui::vec2(value.width, value.height),
+		)
+	}
+}
+
+impl FromTetra<Rectangle<i32>> for egui::Rect {
+	fn from_tetra(value: Rectangle<i32>) -> Self {
+		egui::Rect::from_min_size(
+			egui::pos2(value.x as f32, value.y as f32),
+			egui::vec2(value.width as f32, value.height as f32),
+		)
+	}
+}
+
+impl FromEgui<egui::Rect> for Rectangle<f32> {
+	fn from_egui(value: egui::Rect) -> Self {
+		Rectangle::new(value.left(), value.top(), value.width(), value.height())
+	}
+}
+
+/// The position and size are truncated towards zero.
+impl FromEgui<egui::Rect> for Rectangle<i32> {
+	fn from_egui(value: egui::Rect) -> Self {
+		Rectangle::new(
+			value.left() as i32,
+			value.top() as i32,
+			value.width() as i32,
+			value.height() as i32,
+		)
+	}
+}
+
+/// Tetra colors and [`egui::Color32`] are both sRGB, but
+/// [`egui::Color32`] has premultiplied alpha, so colors with a low alpha
+/// may lose precision.
+impl FromTetra<Color> for egui::Color32 {
+	fn from_tetra(value: Color) -> Self {
+		egui::Color32::from_rgba_unmultiplied(
+			float_to_u8(value.r),
+			float_to_u8(value.g),
+			float_to_u8(value.b),
+			float_to_u8(value.a),
+		)
+	}
+}
+
+/// [`egui::Rgba`] is linear, so the color's components are converted
+/// from sRGB.
+impl FromTetra<Color> for egui::Rgba {
+	fn from_tetra(value: Color) -> Self {
+		egui::Rgba::from_rgba_unmultiplied(
+			linear_from_gamma(value.r),
+			linear_from_gamma(value.g),
+			linear_from_gamma(value.b),
+			value.a,
+		)
+	}
+}
+
+impl FromEgui<egui::Color32> for Color {
+	fn from_egui(value: egui::Color32) -> Self {
+		let [r, g, b, a] = value.to_srgba_unmultiplied();
+		Color::rgba8(r, g, b, a)
+	}
+}
+
+/// [`egui::Rgba`] is linear, so the color's components are converted
+/// to sRGB.
+impl FromEgui<egui::Rgba> for Color {
+	fn from_egui(value: egui::Rgba) -> Self {
+		let [r, g, b, a] = value.to_rgba_unmultiplied();
+		Color::rgba(
+			gamma_from_linear(r),
+			gamma_from_linear(g),
+			gamma_from_linear(b),
+			a,
+		)
+	}
+}
+
+/// egui doesn't care about every keyboard key, so its listing of keys
+/// is less comprehensive than Tetra's. Keys without an egui equivalent
+/// convert to `None`, and number pad keys convert to their equivalents
+/// on the main keyboard.
+impl FromTetra<Key> for Option<egui::Key> {
+	fn from_tetra(value: Key) -> Self {
+		match value {
+			Key::A => Some(egui::Key::A),
+			Key::B => Some(egui::Key::B),
+			Key::C => Some(egui::Key::C),
+			Key::D => Some(egui::Key::D),
+			Key::E => Some(egui::Key::E),
+			Key::F => Some(egui::Key::F),
+			Key::G => Some(egui::Key::G),
+			Key::H => Some(egui::Key::H),
+			Key::I => Some(egui::Key::I),
+			Key::J => Some(egui::Key::J),
+			Key::K => Some(egui::Key::K),
+			Key::L => Some(egui::Key::L),
+			Key::M => Some(egui::Key::M),
+			Key::N => Some(egui::Key::N),
+			Key::O => Some(egui::Key::O),
+			Key::P => Some(egui::Key::P),
+			Key::Q => Some(egui::Key::Q),
+			Key::R => Some(egui::Key::R),
+			Key::S => Some(egui::Key::S),
+			Key::T => Some(egui::Key::T),
+			Key::U => Some(egui::Key::U),
+			Key::V => Some(egui::Key::V),
+			Key::W => Some(egui::Key::W),
+			Key::X => Some(egui::Key::X),
+			Key::Y => Some(egui::Key::Y),
+			Key::Z => Some(egui::Key::Z),
+			Key::Num0 => Some(egui::Key::Num0),
+			Key::Num1 => Some(egui::Key::Num1),
+			Key::Num2 => Some(egui::Key::Num2),
+			Key::Num3 => Some(egui::Key::Num3),
+			Key::Num4 => Some(egui::Key::Num4),
+			Key::Num5 => Some(egui::Key::Num5),
+			Key::Num6 => Some(egui::Key::Num6),
+			Key::Num7 => Some(egui::Key::Num7),
+			Key::Num8 => Some(egui::Key::Num8),
+			Key::Num9 => Some(egui::Key::Num9),
+			Key::NumPad0 => Some(egui::Key::Num0),
+			Key::NumPad1 => Some(egui::Key::Num1),
+			Key::NumPad2 => Some(egui::Key::Num2),
+			Key::NumPad3 => Some(egui::Key::Num3),
+			Key::NumPad4 => Some(egui::Key::Num4),
+			Key::NumPad5 => Some(egui::Key::Num5),
+			Key::NumPad6 => Some(egui::Key::Num6),
+			Key::NumPad7 => Some(egui::Key::Num7),
+			Key::NumPad8 => Some(egui::Key::Num8),
+			Key::NumPad9 => Some(egui::Key::Num9),
+			Key::NumPadEnter => Some(egui::Key::Enter),
+			Key::Up => Some(egui::Key::ArrowUp),
+			Key::Down => Some(egui::Key::ArrowDown),
+			Key::Left => Some(egui::Key::ArrowLeft),
+			Key::Right => Some(egui::Key::ArrowRight),
+			Key::Backspace => Some(egui::Key::Backspace),
+			Key::Delete => Some(egui::Key::Delete),
+			Key::End => Some(egui::Key::End),
+			Key::Enter => Some(egui::Key::Enter),
+			Key::Escape => Some(egui::Key::Escape),
+			Key::Home => Some(egui::Key::Home),
+			Key::Insert => Some(egui::Key::Insert),
+			Key::PageDown => Some(egui::Key::PageDown),
+			Key::PageUp => Some(egui::Key::PageUp),
+			Key::Space => Some(egui::Key::Space),
+			Key::Tab => Some(egui::Key::Tab),
+			_ => None,
+		}
+	}
+}
+
+/// egui's number keys convert to the number keys on the main keyboard,
+/// not the number pad.
+impl FromEgui<egui::Key> for Key {
+	fn from_egui(value: egui::Key) -> Self {
+		match value {
+			egui::Key::ArrowDown => Key::Down,
+			egui::Key::ArrowLeft => Key::Left,
+			egui::Key::ArrowRight => Key::Right,
+			egui::Key::ArrowUp => Key::Up,
+			egui::Key::Escape => Key::Escape,
+			egui::Key::Tab => Key::Tab,
+			egui::Key::Backspace => Key::Backspace,
+			egui::Key::Enter => Key::Enter,
+			egui::Key::Space => Key::Space,
+			egui::Key::Insert => Key::Insert,
+			egui::Key::Delete => Key::Delete,
+			egui::Key::Home => Key::Home,
+			egui::Key::End => Key::End,
+			egui::Key::PageUp => Key::PageUp,
+			egui::Key::PageDown => Key::PageDown,
+			egui::Key::Num0 => Key::Num0,
+			egui::Key::Num1 => Key::Num1,
+			egui::Key::Num2 => Key::Num2,
+			egui::Key::Num3 => Key::Num3,
+			egui::Key::Num4 => Key::Num4,
+			egui::Key::Num5 => Key::Num5,
+			egui::Key::Num6 => Key::Num6,
+			egui::Key::Num7 => Key::Num7,
+			egui::Key::Num8 => Key::Num8,
+			egui::Key::Num9 => Key::Num9,
+			egui::Key::A => Key::A,
+			egui::Key::B => Key::B,
+			egui::Key::C => Key::C,
+			egui::Key::D => Key::D,
+			egui::Key::E => Key::E,
+			egui::Key::F => Key::F,
+			egui::Key::G => Key::G,
+			egui::Key::H => Key::H,
+			egui::Key::I => Key::I,
+			egui::Key::J => Key::J,
+			egui::Key::K => Key::K,
+			egui::Key::L => Key::L,
+			egui::Key::M => Key::M,
+			egui::Key::N => Key::N,
+			egui::Key::O => Key::O,
+			egui::Key::P => Key::P,
+			egui::Key::Q => Key::Q,
+			egui::Key::R => Key::R,
+			egui::Key::S => Key::S,
+			egui::Key::T => Key::T,
+			egui::Key::U => Key::U,
+			egui::Key::V => Key::V,
+			egui::Key::W => Key::W,
+			egui::Key::X => Key::X,
+			egui::Key::Y => Key::Y,
+			egui::Key::Z => Key::Z,
+		}
+	}
+}
+
+/// egui only supports left, middle, and right buttons, so other
+/// buttons convert to `None`.
+impl FromTetra<MouseButton> for Option<egui::PointerButton> {
+	fn from_tetra(value: MouseButton) -> Self {
+		match value {
+			MouseButton::Left => Some(egui::PointerButton::Primary),
+			MouseButton::Middle => Some(egui::PointerButton::Middle),
+			MouseButton::Right => Some(egui::PointerButton::Secondary),
+			_ => None,
+		}
+	}
+}
+
+impl FromEgui<egui::PointerButton> for MouseButton {
+	fn from_egui(value: egui::PointerButton) -> Self {
+		match value {
+			egui::PointerButton::Primary => MouseButton::Left,
+			egui::PointerButton::Middle => MouseButton::Middle,
+			egui::PointerButton::Secondary => MouseButton::Right,
+		}
+	}
+}
+
+fn float_to_u8(value: f32) -> u8 {
+	(value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,327 @@
+use tetra::{
+	graphics::{Color, Rectangle},
+	math::{Mat4, Vec2},
+};
+
+use crate::{convert::FromTetra, world_to_screen};
+
+/// The category used by [`DebugDraw`]'s own drawing methods.
+pub const DEFAULT_DEBUG_CATEGORY: &str = "default";
+
+#[derive(Debug, Clone)]
+enum DebugShape {
+	Line {
+		start: Vec2<f32>,
+		end: Vec2<f32>,
+		color: Color,
+	},
+	Rect {
+		rectangle: Rectangle<f32>,
+		color: Color,
+	},
+	Circle {
+		center: Vec2<f32>,
+		radius: f32,
+		color: Color,
+	},
+	Text {
+		position: Vec2<f32>,
+		text: String,
+		color: Color,
+	},
+}
+
+#[derive(Debug, Clone)]
+struct Category {
+	name: String,
+	visible: bool,
+}
+
+/// Collects lines, rectangles, circles, and text in world coordinates
+/// from gameplay code and paints them with egui, so debug
+/// visualizations like hitboxes and paths don't need their own
+/// Tetra meshes.
+///
+/// Shapes are usually added in [`State::update`](crate::State::update),
+/// after calling [`clear`](DebugDraw::clear) to remove the previous
+/// update's shapes, and painted on egui's background layer by calling
+/// [`show`](DebugDraw::show) from [`State::ui`](crate::State::ui).
+/// `show` doesn't remove the shapes, so they stay visible while gameplay
+/// is paused with [`TimeControls`](crate::TimeControls), and only the
+/// latest update's shapes are painted when it runs more than once
+/// per frame.
+///
+/// Shapes can be grouped into named categories with
+/// [`category`](DebugDraw::category), and each category can be hidden
+/// with [`set_category_visible`](DebugDraw::set_category_visible) or the
+/// checkboxes shown by [`ui`](DebugDraw::ui).
+///
+/// ```
+/// # fn example(debug_draw: &mut egui_tetra::DebugDraw, egui_ctx: &egui_tetra::egui::CtxRef, camera: &tetra::graphics::Camera) {
+/// use tetra::{graphics::{Color, Rectangle}, math::Vec2};
+///
+/// // in update
+/// debug_draw.clear();
+/// debug_draw
+/// 	.category("hitboxes")
+/// 	.rect(Rectangle::new(10.0, 10.0, 32.0, 32.0), Color::RED);
+/// debug_draw.line(Vec2::new(0.0, 0.0), Vec2::new(100.0, 50.0), Color::GREEN);
+/// debug_draw.text(Vec2::new(26.0, 0.0), "player");
+///
+/// // in ui
+/// debug_draw.show(egui_ctx, camera.as_matrix());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DebugDraw {
+	shapes: Vec<(usize, DebugShape)>,
+	categories: Vec<Category>,
+	line_width: f32,
+}
+
+impl DebugDraw {
+	/// Creates a new [`DebugDraw`] with no shapes.
+	pub fn new() -> Self {
+		Self {
+			shapes: vec![],
+			categories: vec![],
+			line_width: 1.5,
+		}
+	}
+
+	/// Sets the width of lines and outlines in points and returns
+	/// the [`DebugDraw`].
+	///
+	/// Defaults to `1.5`.
+	pub fn line_width(mut self, line_width: f32) -> Self {
+		self.line_width = line_width;
+		self
+	}
+
+	/// Returns a [`DebugDrawCategory`] for adding shapes to the category
+	/// with the given name.
+	///
+	/// Categories are created the first time they're used, and are
+	/// visible by default.
+	pub fn category(&mut self, name: &str) -> DebugDrawCategory<'_> {
+		let index = match self
+			.categories
+			.iter()
+			.position(|category| category.name == name)
+		{
+			Some(index) => index,
+			None => {
+				self.categories.push(Category {
+					name: name.to_string(),
+					visible: true,
+				});
+				self.categories.len() - 1
+			}
+		};
+		DebugDrawCategory {
+			debug_draw: self,
+			index,
+		}
+	}
+
+	/// Returns the names of the categories that have been used, in the
+	/// order they were first used.
+	pub fn categories(&self) -> impl Iterator<Item = &str> {
+		self.categories
+			.iter()
+			.map(|category| category.name.as_str())
+	}
+
+	/// Returns `true` if the category with the given name is shown.
+	///
+	/// Categories that haven't been used yet are shown.
+	pub fn is_category_visible(&self, name: &str) -> bool {
+		!self
+			.categories
+			.iter()
+			.any(|category| category.name == name && !category.visible)
+	}
+
+	/// Sets whether the category with the given name is shown.
+	pub fn set_category_visible(&mut self, name: &str, visible: bool) {
+		let index = self.category(name).index;
+		self.categories[index].visible = visible;
+	}
+
+	/// Adds a line between two points to the default category.
+	pub fn line(&mut self, start: Vec2<f32>, end: Vec2<f32>, color: Color) {
+		self.category(DEFAULT_DEBUG_CATEGORY)
+			.line(start, end, color);
+	}
+
+	/// Adds the outline of a rectangle to the default category.
+	pub fn rect(&mut self, rectangle: Rectangle<f32>, color: Color) {
+		self.category(DEFAULT_DEBUG_CATEGORY).rect(rectangle, color);
+	}
+
+	/// Adds the outline of a circle to the default category.
+	pub fn circle(&mut self, center: Vec2<f32>, radius: f32, color: Color) {
+		self.category(DEFAULT_DEBUG_CATEGORY)
+			.circle(center, radius, color);
+	}
+
+	/// Adds white text centered on a point to the default category.
+	pub fn text(&mut self, position: Vec2<f32>, text: impl Into<String>) {
+		self.category(DEFAULT_DEBUG_CATEGORY).text(position, text);
+	}
+
+	/// Adds colored text centered on a point to the default category.
+	pub fn colored_text(&mut self, position: Vec2<f32>, text: impl Into<String>, color: Color) {
+		self.category(DEFAULT_DEBUG_CATEGORY)
+			.colored_text(position, text, color);
+	}
+
+	/// Removes all of the shapes.
+	///
+	/// This should be called at the start of each update, before adding
+	/// that update's shapes.
+	pub fn clear(&mut self) {
+		self.shapes.clear();
+	}
+
+	/// Paints the shapes in visible categories on egui's background
+	/// layer.
+	///
+	/// The shapes are positioned using a transform matrix, usually from a
+	/// [`Camera`](tetra::graphics::Camera). Line widths and text sizes
+	/// aren't affected by the transform.
+	pub fn show(&self, egui_ctx: &egui::CtxRef, transform: Mat4<f32>) {
+		let painter = egui_ctx.layer_painter(egui::LayerId::new(
+			egui::Order::Background,
+			egui::Id::new("egui_tetra_debug_draw"),
+		));
+		let to_screen =
+			|position: Vec2<f32>| egui::Pos2::from_tetra(world_to_screen(transform, position));
+		for (category, shape) in &self.shapes {
+			if !self.categories[*category].visible {
+				continue;
+			}
+			match *shape {
+				DebugShape::Line { start, end, color } => {
+					painter.line_segment(
+						[to_screen(start), to_screen(end)],
+						egui::Stroke::new(self.line_width, egui::Color32::from_tetra(color)),
+					);
+				}
+				DebugShape::Rect { rectangle, color } => {
+					// the transform may rotate the rectangle, so it's
+					// painted as a polygon
+					let corners = [
+						rectangle.top_left(),
+						rectangle.top_right(),
+						rectangle.bottom_right(),
+						rectangle.bottom_left(),
+					];
+					painter.add(egui::Shape::closed_line(
+						corners.iter().map(|corner| to_screen(*corner)).collect(),
+						egui::Stroke::new(self.line_width, egui::Color32::from_tetra(color)),
+					));
+				}
+				DebugShape::Circle {
+					center,
+					radius,
+					color,
+				} => {
+					let screen_center = to_screen(center);
+					let screen_radius =
+						screen_center.distance(to_screen(center + Vec2::new(radius, 0.0)));
+					painter.circle_stroke(
+						screen_center,
+						screen_radius,
+						egui::Stroke::new(self.line_width, egui::Color32::from_tetra(color)),
+					);
+				}
+				DebugShape::Text {
+					position,
+					ref text,
+					color,
+				} => {
+					painter.text(
+						to_screen(position),
+						egui::Align2::CENTER_CENTER,
+						text,
+						egui::TextStyle::Body,
+						egui::Color32::from_tetra(color),
+					);
+				}
+			}
+		}
+	}
+
+	/// Shows a checkbox for each category that controls whether the
+	/// category is shown.
+	pub fn ui(&mut self, ui: &mut egui::Ui) {
+		if self.categories.is_empty() {
+			ui.label("Nothing has been drawn yet");
+		}
+		for category in &mut self.categories {
+			ui.checkbox(&mut category.visible, &category.name);
+		}
+	}
+
+	fn push(&mut self, category: usize, shape: DebugShape) {
+		// skip collecting shapes that would never be painted
+		if crate::UI_ENABLED {
+			self.shapes.push((category, shape));
+		}
+	}
+}
+
+impl Default for DebugDraw {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Adds shapes to a category of a [`DebugDraw`].
+///
+/// This is returned by [`DebugDraw::category`].
+pub struct DebugDrawCategory<'a> {
+	debug_draw: &'a mut DebugDraw,
+	index: usize,
+}
+
+impl DebugDrawCategory<'_> {
+	/// Adds a line between two points and returns the category.
+	pub fn line(self, start: Vec2<f32>, end: Vec2<f32>, color: Color) -> Self {
+		self.push(DebugShape::Line { start, end, color })
+	}
+
+	/// Adds the outline of a rectangle and returns the category.
+	pub fn rect(self, rectangle: Rectangle<f32>, color: Color) -> Self {
+		self.push(DebugShape::Rect { rectangle, color })
+	}
+
+	/// Adds the outline of a circle and returns the category.
+	pub fn circle(self, center: Vec2<f32>, radius: f32, color: Color) -> Self {
+		self.push(DebugShape::Circle {
+			center,
+			radius,
+			color,
+		})
+	}
+
+	/// Adds white text centered on a point and returns the category.
+	pub fn text(self, position: Vec2<f32>, text: impl Into<String>) -> Self {
+		self.colored_text(position, text, Color::WHITE)
+	}
+
+	/// Adds colored text centered on a point and returns the category.
+	pub fn colored_text(self, position: Vec2<f32>, text: impl Into<String>, color: Color) -> Self {
+		self.push(DebugShape::Text {
+			position,
+			text: text.into(),
+			color,
+		})
+	}
+
+	fn push(self, shape: DebugShape) -> Self {
+		self.debug_draw.push(self.index, shape);
+		self
+	}
+}
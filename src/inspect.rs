@@ -0,0 +1,298 @@
+use std::{hash::Hash, ops::RangeInclusive};
+
+use egui::emath::Numeric;
+use tetra::{
+	graphics::{Color, DrawParams, Rectangle},
+	math::Vec2,
+};
+
+use crate::ColorEdit;
+
+/// Settings for how an [`Inspect`] implementor shows its editor.
+///
+/// When deriving [`Inspect`], these are set for each field with the
+/// `#[inspect(...)]` attribute.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InspectOptions {
+	range: Option<RangeInclusive<f64>>,
+	step: Option<f64>,
+	read_only: bool,
+}
+
+impl InspectOptions {
+	/// Creates a new [`InspectOptions`] with no range or step that
+	/// allows editing.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the range numbers are limited to and returns the options.
+	///
+	/// Numbers with a range are edited with a slider instead of a
+	/// drag value.
+	pub fn range(mut self, range: RangeInclusive<f64>) -> Self {
+		self.range = Some(range);
+		self
+	}
+
+	/// Sets the step numbers are rounded to and returns the options.
+	pub fn step(mut self, step: f64) -> Self {
+		self.step = Some(step);
+		self
+	}
+
+	/// Sets whether the value is shown without allowing it to be
+	/// edited and returns the options.
+	pub fn read_only(mut self, read_only: bool) -> Self {
+		self.read_only = read_only;
+		self
+	}
+
+	/// Returns the range numbers are limited to, if there is one.
+	pub fn get_range(&self) -> Option<&RangeInclusive<f64>> {
+		self.range.as_ref()
+	}
+
+	/// Returns the step numbers are rounded to, if there is one.
+	pub fn get_step(&self) -> Option<f64> {
+		self.step
+	}
+
+	/// Returns `true` if the value can't be edited.
+	pub fn is_read_only(&self) -> bool {
+		self.read_only
+	}
+}
+
+/// A type that can show an editor for itself in an egui [`Ui`](egui::Ui).
+///
+/// This is implemented for numbers, `bool`, `String`, and Tetra's
+/// [`Vec2`], [`Color`], [`Rectangle`], and [`DrawParams`]. With the `derive`
+/// feature, it can be derived for structs whose fields implement `Inspect`,
+/// and for enums without fields. The derive macro's `#[inspect(...)]`
+/// field attribute accepts `range = <start>..=<end>`,
+/// `step = <step>`, `read_only`, `label = "<label>"`, and `skip`.
+///
+/// Values are usually shown with [`UiInspectExt::inspect`]:
+///
+/// ```
+/// # fn ui(ui: &mut egui_tetra::egui::Ui, gravity: &mut f32) {
+/// use egui_tetra::UiInspectExt;
+///
+/// ui.inspect(gravity);
+/// # }
+/// ```
+pub trait Inspect {
+	/// Shows an editor for the value.
+	///
+	/// The returned response should be marked as
+	/// [changed](egui::Response::changed) if the value was edited.
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response;
+}
+
+/// Adds a method for showing [`Inspect`] implementors to [`egui::Ui`].
+pub trait UiInspectExt {
+	/// Shows an editor for a value with the default [`InspectOptions`].
+	fn inspect<T: Inspect + ?Sized>(&mut self, value: &mut T) -> egui::Response;
+
+	/// Shows an editor for a value with the given [`InspectOptions`].
+	fn inspect_with<T: Inspect + ?Sized>(
+		&mut self,
+		value: &mut T,
+		options: &InspectOptions,
+	) -> egui::Response;
+}
+
+impl UiInspectExt for egui::Ui {
+	fn inspect<T: Inspect + ?Sized>(&mut self, value: &mut T) -> egui::Response {
+		value.inspect(self, &InspectOptions::default())
+	}
+
+	fn inspect_with<T: Inspect + ?Sized>(
+		&mut self,
+		value: &mut T,
+		options: &InspectOptions,
+	) -> egui::Response {
+		value.inspect(self, options)
+	}
+}
+
+/// Adds contents to a child [`Ui`](egui::Ui) with its own ID, so
+/// widgets that store state don't clash with their siblings.
+fn with_id_source<R>(
+	ui: &mut egui::Ui,
+	id_source: impl Hash,
+	add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+	let mut child_ui =
+		ui.child_ui_with_id_source(ui.available_rect_before_wrap(), *ui.layout(), id_source);
+	let inner = add_contents(&mut child_ui);
+	ui.allocate_rect(child_ui.min_rect(), egui::Sense::hover());
+	inner
+}
+
+/// Shows a labeled row in a grid of fields. Used by the `Inspect`
+/// derive macro.
+#[doc(hidden)]
+pub fn inspect_field<T: Inspect + ?Sized>(
+	ui: &mut egui::Ui,
+	label: &str,
+	value: &mut T,
+	options: &InspectOptions,
+) -> egui::Response {
+	ui.label(label);
+	let response = with_id_source(ui, label, |ui| value.inspect(ui, options));
+	ui.end_row();
+	response
+}
+
+/// Shows a grid of labeled fields, marking the grid's response as
+/// changed if any of the fields changed. Used by the `Inspect`
+/// derive macro.
+#[doc(hidden)]
+pub fn inspect_fields(
+	ui: &mut egui::Ui,
+	id_source: &str,
+	add_fields: impl FnOnce(&mut egui::Ui) -> bool,
+) -> egui::Response {
+	let egui::InnerResponse {
+		inner: changed,
+		mut response,
+	} = egui::Grid::new(id_source)
+		.num_columns(2)
+		.show(ui, add_fields);
+	if changed {
+		response.mark_changed();
+	}
+	response
+}
+
+fn inspect_number<N: Numeric>(
+	value: &mut N,
+	ui: &mut egui::Ui,
+	options: &InspectOptions,
+) -> egui::Response {
+	let step = options.step.filter(|step| *step > 0.0);
+	let get_set = |new_value: Option<f64>| {
+		if let Some(mut new_value) = new_value {
+			if let Some(step) = step {
+				new_value = (new_value / step).round() * step;
+			}
+			*value = N::from_f64(new_value);
+		}
+		value.to_f64()
+	};
+	match &options.range {
+		Some(range) => {
+			let mut slider = egui::Slider::from_get_set(range.clone(), get_set);
+			if N::INTEGRAL {
+				slider = slider.integer();
+			}
+			ui.add_enabled(!options.read_only, slider)
+		}
+		None => {
+			let mut drag_value = egui::DragValue::from_get_set(get_set)
+				.clamp_range(N::MIN.to_f64()..=N::MAX.to_f64())
+				.speed(step.unwrap_or(if N::INTEGRAL { 0.25 } else { 0.1 }));
+			if N::INTEGRAL {
+				drag_value = drag_value.max_decimals(0);
+			}
+			ui.add_enabled(!options.read_only, drag_value)
+		}
+	}
+}
+
+macro_rules! impl_inspect_for_numbers {
+	($($t:ty),*) => {
+		$(
+			impl Inspect for $t {
+				fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+					inspect_number(self, ui, options)
+				}
+			}
+		)*
+	};
+}
+
+impl_inspect_for_numbers!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl Inspect for bool {
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+		ui.add_enabled(!options.read_only, egui::Checkbox::new(self, ""))
+	}
+}
+
+impl Inspect for String {
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+		ui.add_enabled(!options.read_only, egui::TextEdit::singleline(self))
+	}
+}
+
+impl<T: Inspect> Inspect for Vec2<T> {
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+		let egui::InnerResponse {
+			inner: changed,
+			mut response,
+		} = ui.horizontal(|ui| {
+			ui.label("x");
+			let x = with_id_source(ui, "x", |ui| self.x.inspect(ui, options));
+			ui.label("y");
+			let y = with_id_source(ui, "y", |ui| self.y.inspect(ui, options));
+			x.changed() || y.changed()
+		});
+		if changed {
+			response.mark_changed();
+		}
+		response
+	}
+}
+
+impl<T: Inspect> Inspect for Rectangle<T> {
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+		inspect_fields(ui, "egui_tetra_inspect_rectangle", |ui| {
+			let x = inspect_field(ui, "x", &mut self.x, options);
+			let y = inspect_field(ui, "y", &mut self.y, options);
+			let width = inspect_field(ui, "width", &mut self.width, options);
+			let height = inspect_field(ui, "height", &mut self.height, options);
+			x.changed() || y.changed() || width.changed() || height.changed()
+		})
+	}
+}
+
+impl Inspect for Color {
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+		ui.add_enabled(
+			!options.read_only,
+			ColorEdit::new("egui_tetra_inspect_color", self),
+		)
+	}
+}
+
+/// The range and step of the options apply to the position, scale,
+/// and origin.
+impl Inspect for DrawParams {
+	fn inspect(&mut self, ui: &mut egui::Ui, options: &InspectOptions) -> egui::Response {
+		let other_options = InspectOptions::new().read_only(options.read_only);
+		inspect_fields(ui, "egui_tetra_inspect_draw_params", |ui| {
+			let position = inspect_field(ui, "position", &mut self.position, options);
+			let scale = inspect_field(ui, "scale", &mut self.scale, options);
+			let origin = inspect_field(ui, "origin", &mut self.origin, options);
+			ui.label("rotation");
+			let mut degrees = self.rotation.to_degrees();
+			let rotation = ui.add_enabled(
+				!options.read_only,
+				egui::DragValue::new(&mut degrees).speed(1.0).suffix("°"),
+			);
+			if rotation.changed() {
+				self.rotation = degrees.to_radians();
+			}
+			ui.end_row();
+			let color = inspect_field(ui, "color", &mut self.color, &other_options);
+			position.changed()
+				|| scale.changed()
+				|| origin.changed()
+				|| rotation.changed()
+				|| color.changed()
+		})
+	}
+}
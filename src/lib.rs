@@ -116,41 +116,152 @@
 //!
 //! If you need more control, you can use [`EguiWrapper`] and manually
 //! hook up egui to Tetra's callbacks.
+//!
+//! ## Debug UI
+//!
+//! If the GUI is only used for debugging tools, enable the `debug-ui`
+//! feature. In release builds, this makes [`UI_ENABLED`] `false`, which
+//! turns the egui integration into no-ops:
+//!
+//! - [`StateWrapper`] passes all input straight to the game and never
+//!   calls the GUI callbacks. It keeps a single, empty egui context to pass
+//!   to the [`State`] and [`Plugin`] callbacks, but never begins a frame with
+//!   it, so egui never loads its fonts, lays out a GUI, or draws anything.
+//!   Contexts added with [`StateWrapper::add_context`] are discarded.
+//! - [`EguiWrapper`]'s methods do nothing.
+//!
+//! egui and the crate's other dependencies are still compiled, since egui's
+//! types are part of the API, but because [`UI_ENABLED`] is a constant,
+//! the code that uses them (including the clipboard and URL opening code)
+//! is removed from optimized builds. Code using [`EguiWrapper`] directly
+//! can check [`UI_ENABLED`] to skip its GUI code as well.
 
 #![warn(missing_docs)]
 #![allow(clippy::tabs_in_doc_comments)]
 
+mod commands;
+mod context;
+pub mod convert;
+mod debug_draw;
+mod inspect;
+#[cfg(feature = "log")]
+mod logger;
+mod panels;
+mod plugin;
+#[cfg(feature = "replay")]
+mod replay;
+mod scene;
+mod script;
+mod state;
+mod stats;
+mod time_controls;
+mod viewport;
+mod widgets;
+mod world;
+
+pub use commands::{ArgType, ArgValue, Command, CommandArgs, CommandRegistry};
+pub use context::{NamedContext, MAIN_CONTEXT};
+pub use debug_draw::{DebugDraw, DebugDrawCategory, DEFAULT_DEBUG_CATEGORY};
 pub use egui;
+#[cfg(feature = "derive")]
+pub use egui_tetra_derive::Inspect;
+pub use inspect::{Inspect, InspectOptions, UiInspectExt};
+#[cfg(feature = "log")]
+pub use logger::{LogBuffer, LogRecord, Logger};
+#[cfg(feature = "log")]
+pub use panels::LogPanel;
+pub use panels::{
+	ConsolePanel, EventLogPanel, InputPanel, PerformancePanel, TimeControlPanel, WindowPanel,
+};
+#[cfg(feature = "audio")]
+pub use panels::{RegisteredSound, SoundRegistry};
+pub use plugin::{EventDelivery, EventRecord, Plugin};
+#[cfg(feature = "replay")]
+pub use replay::{InputRecording, RecordedEvent};
+pub use scene::{Scene, SceneStack, Transition};
+pub use script::{ScriptTarget, UiScript};
+pub use state::{State, StateWrapper};
+pub use stats::FrameStats;
+pub use time_controls::{TimeControls, MAX_TIME_SCALE};
+pub use viewport::Viewport;
+pub use widgets::{
+	ColorEdit, DrawParamsEdit, GizmoMode, GizmoTransform, RectangleEdit, SpriteSheetSlicer,
+	TextureViewer, TransformGizmo, Vec2Edit,
+};
+pub use world::{screen_to_world, world_to_screen, WorldArea};
+
+/// Items used by code generated by the `Inspect` derive macro.
+#[doc(hidden)]
+pub mod __inspect {
+	pub use crate::inspect::{inspect_field, inspect_fields};
+}
 
-use std::{fmt::Display, sync::Arc, time::Instant};
+use std::{collections::HashMap, fmt::Display, ops::RangeBounds, sync::Arc, time::Instant};
 
+use convert::{FromEgui, FromTetra};
 use copypasta::{ClipboardContext, ClipboardProvider};
-use egui::{ClippedMesh, CtxRef, RawInput};
+use egui::{epaint::ClippedShape, ClippedMesh, CtxRef, Order, RawInput};
 use tetra::{
 	graphics::{self, BlendAlphaMode, BlendMode},
-	Event, TetraError,
+	math::{Mat4, Vec2},
+	TetraError,
 };
 
+/// Whether the egui integration is active.
+///
+/// This is `false` in release builds when the `debug-ui` feature
+/// is enabled, and `true` otherwise.
+pub const UI_ENABLED: bool = !cfg!(all(feature = "debug-ui", not(debug_assertions)));
+
 const SCROLL_SENSITIVITY: f32 = 48.0;
 const ZOOM_SENSITIVITY: f32 = 1.25;
 
-fn tetra_vec2_to_egui_pos2(tetra_vec2: tetra::math::Vec2<f32>) -> egui::Pos2 {
-	egui::pos2(tetra_vec2.x, tetra_vec2.y)
-}
+/// Every egui layer [`Order`], from back to front.
+///
+/// egui's own list of orders isn't public, so this has to be kept in
+/// sync with it (which the tests check).
+const ORDERS: [Order; 6] = [
+	Order::Background,
+	Order::PanelResizeLine,
+	Order::Middle,
+	Order::Foreground,
+	Order::Tooltip,
+	Order::Debug,
+];
 
-fn egui_pos2_to_tetra_vec2(egui_pos2: egui::Pos2) -> tetra::math::Vec2<f32> {
-	tetra::math::Vec2::new(egui_pos2.x, egui_pos2.y)
-}
+/// The first of the user texture IDs reserved for the empty meshes
+/// that mark where each [`Order`] starts in egui's output.
+const LAYER_MARKER_TEXTURE_ID: u64 = u64::MAX - ORDERS.len() as u64;
 
-fn egui_rect_to_tetra_rectangle(egui_rect: egui::Rect) -> tetra::graphics::Rectangle<i32> {
+/// Returns the bounding box of a rectangle after it's been
+/// transformed by a matrix.
+fn transform_rectangle(
+	transform: Mat4<f32>,
+	rectangle: tetra::graphics::Rectangle<i32>,
+) -> tetra::graphics::Rectangle<i32> {
+	let corners = [
+		(rectangle.left(), rectangle.top()),
+		(rectangle.right(), rectangle.top()),
+		(rectangle.left(), rectangle.bottom()),
+		(rectangle.right(), rectangle.bottom()),
+	]
+	.map(|(x, y)| world_to_screen(transform, Vec2::new(x as f32, y as f32)));
+	let min = corners
+		.iter()
+		.fold(corners[0], |min, corner| min.map2(*corner, f32::min));
+	let max = corners
+		.iter()
+		.fold(corners[0], |max, corner| max.map2(*corner, f32::max));
 	tetra::graphics::Rectangle::new(
-		egui_rect.left() as i32,
-		egui_rect.top() as i32,
-		egui_rect.width() as i32,
-		egui_rect.height() as i32,
+		min.x.floor() as i32,
+		min.y.floor() as i32,
+		(max.x - min.x).ceil() as i32,
+		(max.y - min.y).ceil() as i32,
 	)
 }
 
+/// Converts a vertex color without unmultiplying its alpha, since
+/// egui's meshes are drawn with premultiplied alpha blending.
 fn egui_color32_to_tetra_color(egui_color: egui::Color32) -> tetra::graphics::Color {
 	tetra::graphics::Color::rgba8(
 		egui_color.r(),
@@ -160,96 +271,6 @@ fn egui_color32_to_tetra_color(egui_color: egui::Color32) -> tetra::graphics::Co
 	)
 }
 
-/// Converts a [tetra key](tetra::input::Key) to an
-/// [egui key](egui::Key) if there's an egui equivalent
-/// to the tetra key, otherwise returns `None`.
-///
-/// egui doesn't care about every keyboard key, so its listing of keys
-/// is less comprehensive than tetra's.
-fn tetra_key_to_egui_key(key: tetra::input::Key) -> Option<egui::Key> {
-	match key {
-		tetra::input::Key::A => Some(egui::Key::A),
-		tetra::input::Key::B => Some(egui::Key::B),
-		tetra::input::Key::C => Some(egui::Key::C),
-		tetra::input::Key::D => Some(egui::Key::D),
-		tetra::input::Key::E => Some(egui::Key::E),
-		tetra::input::Key::F => Some(egui::Key::F),
-		tetra::input::Key::G => Some(egui::Key::G),
-		tetra::input::Key::H => Some(egui::Key::H),
-		tetra::input::Key::I => Some(egui::Key::I),
-		tetra::input::Key::J => Some(egui::Key::J),
-		tetra::input::Key::K => Some(egui::Key::K),
-		tetra::input::Key::L => Some(egui::Key::L),
-		tetra::input::Key::M => Some(egui::Key::M),
-		tetra::input::Key::N => Some(egui::Key::N),
-		tetra::input::Key::O => Some(egui::Key::O),
-		tetra::input::Key::P => Some(egui::Key::P),
-		tetra::input::Key::Q => Some(egui::Key::Q),
-		tetra::input::Key::R => Some(egui::Key::R),
-		tetra::input::Key::S => Some(egui::Key::S),
-		tetra::input::Key::T => Some(egui::Key::T),
-		tetra::input::Key::U => Some(egui::Key::U),
-		tetra::input::Key::V => Some(egui::Key::V),
-		tetra::input::Key::W => Some(egui::Key::W),
-		tetra::input::Key::X => Some(egui::Key::X),
-		tetra::input::Key::Y => Some(egui::Key::Y),
-		tetra::input::Key::Z => Some(egui::Key::Z),
-		tetra::input::Key::Num0 => Some(egui::Key::Num0),
-		tetra::input::Key::Num1 => Some(egui::Key::Num1),
-		tetra::input::Key::Num2 => Some(egui::Key::Num2),
-		tetra::input::Key::Num3 => Some(egui::Key::Num3),
-		tetra::input::Key::Num4 => Some(egui::Key::Num4),
-		tetra::input::Key::Num5 => Some(egui::Key::Num5),
-		tetra::input::Key::Num6 => Some(egui::Key::Num6),
-		tetra::input::Key::Num7 => Some(egui::Key::Num7),
-		tetra::input::Key::Num8 => Some(egui::Key::Num8),
-		tetra::input::Key::Num9 => Some(egui::Key::Num9),
-		tetra::input::Key::NumPad0 => Some(egui::Key::Num0),
-		tetra::input::Key::NumPad1 => Some(egui::Key::Num1),
-		tetra::input::Key::NumPad2 => Some(egui::Key::Num2),
-		tetra::input::Key::NumPad3 => Some(egui::Key::Num3),
-		tetra::input::Key::NumPad4 => Some(egui::Key::Num4),
-		tetra::input::Key::NumPad5 => Some(egui::Key::Num5),
-		tetra::input::Key::NumPad6 => Some(egui::Key::Num6),
-		tetra::input::Key::NumPad7 => Some(egui::Key::Num7),
-		tetra::input::Key::NumPad8 => Some(egui::Key::Num8),
-		tetra::input::Key::NumPad9 => Some(egui::Key::Num9),
-		tetra::input::Key::NumPadEnter => Some(egui::Key::Enter),
-		tetra::input::Key::Up => Some(egui::Key::ArrowUp),
-		tetra::input::Key::Down => Some(egui::Key::ArrowDown),
-		tetra::input::Key::Left => Some(egui::Key::ArrowLeft),
-		tetra::input::Key::Right => Some(egui::Key::ArrowRight),
-		tetra::input::Key::Backspace => Some(egui::Key::Backspace),
-		tetra::input::Key::Delete => Some(egui::Key::Delete),
-		tetra::input::Key::End => Some(egui::Key::End),
-		tetra::input::Key::Enter => Some(egui::Key::Enter),
-		tetra::input::Key::Escape => Some(egui::Key::Escape),
-		tetra::input::Key::Home => Some(egui::Key::Home),
-		tetra::input::Key::Insert => Some(egui::Key::Insert),
-		tetra::input::Key::PageDown => Some(egui::Key::PageDown),
-		tetra::input::Key::PageUp => Some(egui::Key::PageUp),
-		tetra::input::Key::Space => Some(egui::Key::Space),
-		tetra::input::Key::Tab => Some(egui::Key::Tab),
-		_ => None,
-	}
-}
-
-/// Converts a [tetra mouse button](tetra::input::MouseButton) to an
-/// [egui mouse button](egui::PointerButton) if there's an egui equivalent
-/// to the tetra mouse button, otherwise returns `None`.
-///
-/// egui only supports left, middle, and right buttons.
-fn tetra_mouse_button_to_egui_pointer_button(
-	tetra_mouse_button: tetra::input::MouseButton,
-) -> Option<egui::PointerButton> {
-	match tetra_mouse_button {
-		tetra::input::MouseButton::Left => Some(egui::PointerButton::Primary),
-		tetra::input::MouseButton::Middle => Some(egui::PointerButton::Middle),
-		tetra::input::MouseButton::Right => Some(egui::PointerButton::Secondary),
-		_ => None,
-	}
-}
-
 fn egui_mesh_to_tetra_mesh(
 	ctx: &mut tetra::Context,
 	egui_mesh: egui::epaint::Mesh,
@@ -261,8 +282,8 @@ fn egui_mesh_to_tetra_mesh(
 		.iter()
 		.map(|vertex| {
 			tetra::graphics::mesh::Vertex::new(
-				egui_pos2_to_tetra_vec2(vertex.pos),
-				egui_pos2_to_tetra_vec2(vertex.uv),
+				Vec2::from_egui(vertex.pos),
+				Vec2::from_egui(vertex.uv),
 				egui_color32_to_tetra_color(vertex.color),
 			)
 		})
@@ -299,6 +320,56 @@ fn egui_font_image_to_tetra_texture(
 	)
 }
 
+/*
+egui doesn't tell us which layer each shape it outputs belongs to,
+but it does output the shapes sorted by layer order: when a frame
+ends, egui drains the shapes one order at a time, from back to front,
+and within each order from the bottom area to the top one. To figure
+out where each order starts, every frame we show an invisible area
+at the start of each order containing an empty mesh with a reserved
+texture ID. Since these areas are created before any others (and
+can't be clicked to bring them to the front), egui keeps them at the
+bottom of their order. If egui ever changes how it sorts its output,
+the layer_order_groups test should catch it.
+*/
+
+/// Shows the areas that mark the start of each layer [`Order`].
+fn mark_layer_orders(egui_ctx: &CtxRef) {
+	for (i, order) in ORDERS.iter().enumerate() {
+		egui::Area::new(egui::Id::new("egui_tetra_layer_marker").with(i))
+			.order(*order)
+			.interactable(false)
+			.fixed_pos(egui::pos2(-1.0, -1.0))
+			.show(egui_ctx, |ui| {
+				ui.painter()
+					.add(egui::Shape::mesh(egui::epaint::Mesh::with_texture(
+						egui::TextureId::User(LAYER_MARKER_TEXTURE_ID + i as u64),
+					)));
+			});
+	}
+}
+
+/// Splits the shapes output by egui into groups by layer [`Order`],
+/// using the markers added by [`mark_layer_orders`].
+fn group_shapes_by_order(shapes: Vec<ClippedShape>) -> Vec<(Order, Vec<ClippedShape>)> {
+	let mut groups = vec![(Order::Background, vec![])];
+	for clipped_shape in shapes {
+		if let egui::Shape::Mesh(mesh) = &clipped_shape.1 {
+			if let egui::TextureId::User(id) = mesh.texture_id {
+				if id >= LAYER_MARKER_TEXTURE_ID {
+					let order = ORDERS[(id - LAYER_MARKER_TEXTURE_ID) as usize];
+					groups.push((order, vec![]));
+					continue;
+				}
+			}
+		}
+		if let Some((_, group)) = groups.last_mut() {
+			group.push(clipped_shape);
+		}
+	}
+	groups
+}
+
 /// An error that can occur when using egui-tetra.
 #[derive(Debug)]
 pub enum Error {
@@ -309,6 +380,17 @@ pub enum Error {
 	OpenError(std::io::Error),
 	/// An error occurred when accessing the system's clipboard.
 	ClipboardError(Box<dyn std::error::Error + Send + Sync>),
+	/// An error occurred when loading or saving an egui context's
+	/// memory.
+	PersistenceError(Box<dyn std::error::Error + Send + Sync>),
+	/// An error occurred when loading or saving an input recording.
+	#[cfg(feature = "replay")]
+	ReplayError(Box<dyn std::error::Error + Send + Sync>),
+	/// A [`UiScript`] action couldn't be performed.
+	ScriptError(String),
+	/// An error occurred when installing the logger for the `log` crate.
+	#[cfg(feature = "log")]
+	LoggerError(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl Display for Error {
@@ -317,6 +399,12 @@ impl Display for Error {
 			Error::TetraError(error) => error.fmt(f),
 			Error::OpenError(error) => error.fmt(f),
 			Error::ClipboardError(error) => error.fmt(f),
+			Error::PersistenceError(error) => error.fmt(f),
+			#[cfg(feature = "replay")]
+			Error::ReplayError(error) => error.fmt(f),
+			Error::ScriptError(message) => message.fmt(f),
+			#[cfg(feature = "log")]
+			Error::LoggerError(error) => error.fmt(f),
 		}
 	}
 }
@@ -327,6 +415,12 @@ impl std::error::Error for Error {
 			Error::TetraError(error) => Some(error),
 			Error::OpenError(error) => Some(error),
 			Error::ClipboardError(error) => Some(error.as_ref()),
+			Error::PersistenceError(error) => Some(error.as_ref()),
+			#[cfg(feature = "replay")]
+			Error::ReplayError(error) => Some(error.as_ref()),
+			Error::ScriptError(_) => None,
+			#[cfg(feature = "log")]
+			Error::LoggerError(error) => Some(error.as_ref()),
 		}
 	}
 }
@@ -349,14 +443,74 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
 	}
 }
 
+/// Converts an error into an [`Error`] and, if the `log` feature is
+/// enabled, logs it, so problems like a failure to access the clipboard
+/// show up in a log panel and not only in the `Error` they cause.
+fn logged(error: impl Into<Error>) -> Error {
+	let error = error.into();
+	#[cfg(feature = "log")]
+	log::error!("{}", error);
+	error
+}
+
+/// A mesh from a finished GUI frame, ready to be drawn.
+struct GuiMesh {
+	order: Order,
+	clip_rect: tetra::graphics::Rectangle<i32>,
+	mesh: tetra::graphics::mesh::Mesh,
+	/// Whether the mesh's texture uses premultiplied alpha.
+	premultiplied: bool,
+}
+
+/// A piece of text from a finished GUI frame, along with its visible
+/// area.
+struct ShownText {
+	rect: egui::Rect,
+	galley: Arc<egui::Galley>,
+}
+
+/// Adds the visible text in a shape to `texts`.
+fn collect_texts(texts: &mut Vec<ShownText>, clip_rect: egui::Rect, shape: &egui::Shape) {
+	match shape {
+		egui::Shape::Vec(shapes) => {
+			for shape in shapes {
+				collect_texts(texts, clip_rect, shape);
+			}
+		}
+		egui::Shape::Text(text) => {
+			let rect = egui::Rect::from_min_size(text.pos, text.galley.size()).intersect(clip_rect);
+			if rect.is_positive() {
+				texts.push(ShownText {
+					rect,
+					galley: text.galley.clone(),
+				});
+			}
+		}
+		_ => {}
+	}
+}
+
 /// Wraps an egui context with features that are useful
 /// for integrating egui with Tetra.
 pub struct EguiWrapper {
 	raw_input: RawInput,
 	ctx: CtxRef,
 	texture: Option<tetra::graphics::Texture>,
+	texture_version: u64,
+	user_textures: HashMap<u64, tetra::graphics::Texture>,
+	next_user_texture_id: u64,
 	last_frame_time: Instant,
-	meshes: Vec<(tetra::graphics::Rectangle<i32>, tetra::graphics::mesh::Mesh)>,
+	meshes: Vec<GuiMesh>,
+	transform: Option<Mat4<f32>>,
+	screen_size: Option<Vec2<f32>>,
+	stats: FrameStats,
+	/// The position of the mouse from the latest mouse movement event.
+	pointer_position: Option<Vec2<f32>>,
+	/// The text shown in the latest finished frame.
+	texts: Vec<ShownText>,
+	/// The widgets registered with [`ScriptTarget`] in the latest
+	/// finished frame.
+	script_targets: Vec<script::Target>,
 }
 
 impl EguiWrapper {
@@ -366,8 +520,17 @@ impl EguiWrapper {
 			raw_input: RawInput::default(),
 			ctx: CtxRef::default(),
 			texture: None,
+			texture_version: 0,
+			user_textures: HashMap::new(),
+			next_user_texture_id: 0,
 			last_frame_time: Instant::now(),
 			meshes: vec![],
+			transform: None,
+			screen_size: None,
+			stats: FrameStats::default(),
+			pointer_position: None,
+			texts: vec![],
+			script_targets: vec![],
 		}
 	}
 
@@ -376,29 +539,146 @@ impl EguiWrapper {
 		&self.ctx
 	}
 
+	/// Returns the transform matrix the GUI is drawn with, if there is one.
+	pub fn transform(&self) -> Option<Mat4<f32>> {
+		self.transform
+	}
+
+	/// Sets a transform matrix to draw the GUI with, or `None` to draw
+	/// the GUI in window coordinates.
+	///
+	/// This can be used to draw a GUI inside the game world, such as on
+	/// a computer screen in a level. Mouse positions are transformed by the
+	/// inverse of the matrix so clicks land in the right place. Usually you'll
+	/// also want to set the [screen size](Self::set_screen_size) to the size
+	/// of the GUI's surface.
+	///
+	/// Scissor rectangles can't be rotated, so rotated GUIs are clipped
+	/// to the bounding boxes of their clip rectangles.
+	pub fn set_transform(&mut self, transform: Option<Mat4<f32>>) {
+		self.transform = transform;
+	}
+
+	/// Returns the size of the area the GUI is laid out in, if it
+	/// has been set.
+	pub fn screen_size(&self) -> Option<Vec2<f32>> {
+		self.screen_size
+	}
+
+	/// Sets the size of the area the GUI is laid out in, or `None` to
+	/// use the size of the window.
+	pub fn set_screen_size(&mut self, screen_size: Option<Vec2<f32>>) {
+		self.screen_size = screen_size;
+	}
+
+	/// Returns statistics about the latest GUI frame.
+	///
+	/// The [draw time](FrameStats::draw_time) only covers the layers drawn
+	/// since the frame ended.
+	pub fn frame_stats(&self) -> FrameStats {
+		self.stats
+	}
+
+	/// Converts a position in window coordinates to a position in
+	/// GUI coordinates, taking the [transform](Self::set_transform)
+	/// into account.
+	pub fn window_to_gui(&self, window_position: Vec2<f32>) -> egui::Pos2 {
+		match self.transform {
+			Some(transform) => egui::Pos2::from_tetra(screen_to_world(transform, window_position)),
+			None => egui::Pos2::from_tetra(window_position),
+		}
+	}
+
+	/// Converts a position in GUI coordinates to a position in
+	/// window coordinates, taking the [transform](Self::set_transform)
+	/// into account.
+	pub fn gui_to_window(&self, gui_position: egui::Pos2) -> Vec2<f32> {
+		match self.transform {
+			Some(transform) => world_to_screen(transform, Vec2::from_egui(gui_position)),
+			None => Vec2::from_egui(gui_position),
+		}
+	}
+
+	/// Returns the rectangle (in GUI coordinates) of the visible text in the
+	/// latest finished frame that exactly matches `text`, or `None` if there
+	/// isn't any.
+	///
+	/// If more than one piece of text matches, the one drawn on top is
+	/// returned. This is useful for finding widgets by their labels, as
+	/// [`UiScript`] does for widgets that aren't registered as a
+	/// [`ScriptTarget`].
+	pub fn find_text(&self, text: &str) -> Option<egui::Rect> {
+		self.find_texts(text).pop()
+	}
+
+	/// Returns the rectangles of the visible text in the latest finished
+	/// frame that exactly matches `text`, from bottom to top.
+	fn find_texts(&self, text: &str) -> Vec<egui::Rect> {
+		self.texts
+			.iter()
+			.filter(|shown| shown.galley.text() == text)
+			.map(|shown| shown.rect)
+			.collect()
+	}
+
+	/// Records the text and [script targets](ScriptTarget) of a
+	/// finished frame.
+	fn record_frame(&mut self, shapes: &[ClippedShape]) {
+		self.texts.clear();
+		for ClippedShape(clip_rect, shape) in shapes {
+			collect_texts(&mut self.texts, *clip_rect, shape);
+		}
+		self.script_targets = self
+			.ctx
+			.memory()
+			.data
+			.get_temp::<script::Targets>(script::Targets::id())
+			.map_or_else(Vec::new, |targets| targets.0);
+		self.ctx
+			.memory()
+			.data
+			.remove::<script::Targets>(script::Targets::id());
+	}
+
+	/// Makes a Tetra [`Texture`](tetra::graphics::Texture) available to egui
+	/// and returns an ID that can be used to display it, for example with an
+	/// [`egui::Image`].
+	///
+	/// To display a [`Canvas`](tetra::graphics::Canvas), register its
+	/// [texture](tetra::graphics::Canvas::texture).
+	///
+	/// Unlike egui's own textures, user textures are expected to use straight
+	/// (non-premultiplied) alpha, like other Tetra textures.
+	pub fn register_texture(&mut self, texture: tetra::graphics::Texture) -> egui::TextureId {
+		let id = self.next_user_texture_id;
+		self.next_user_texture_id += 1;
+		self.user_textures.insert(id, texture);
+		egui::TextureId::User(id)
+	}
+
+	/// Stops a texture registered with [`register_texture`](Self::register_texture)
+	/// from being available to egui.
+	///
+	/// Shapes that use the texture will no longer be drawn.
+	pub fn unregister_texture(&mut self, texture_id: egui::TextureId) {
+		if let egui::TextureId::User(id) = texture_id {
+			self.user_textures.remove(&id);
+		}
+	}
+
 	/// Dispaches a Tetra [`Event`](tetra::Event) to the egui context.
 	pub fn event(&mut self, ctx: &tetra::Context, event: &tetra::Event) -> Result<(), Error> {
+		if !UI_ENABLED {
+			return Ok(());
+		}
 		match event {
 			tetra::Event::KeyPressed { key } => {
-				// update modifiers
-				match key {
-					tetra::input::Key::LeftCtrl | tetra::input::Key::RightCtrl => {
-						self.raw_input.modifiers.ctrl = true;
-						self.raw_input.modifiers.command = true;
-					}
-					tetra::input::Key::LeftShift | tetra::input::Key::RightShift => {
-						self.raw_input.modifiers.shift = true;
-					}
-					tetra::input::Key::LeftAlt | tetra::input::Key::RightAlt => {
-						self.raw_input.modifiers.alt = true;
-					}
-					_ => {}
-				}
+				self.update_modifiers(event);
 
 				// copy/cut/paste
-				if tetra::input::is_key_down(ctx, tetra::input::Key::LeftCtrl)
-					| tetra::input::is_key_down(ctx, tetra::input::Key::RightCtrl)
-				{
+				// the modifiers come from the events rather than the real
+				// keyboard, so replayed input behaves the same every time
+				if self.raw_input.modifiers.ctrl {
 					if let tetra::input::Key::C = key {
 						self.raw_input.events.push(egui::Event::Copy);
 					}
@@ -406,13 +686,16 @@ impl EguiWrapper {
 						self.raw_input.events.push(egui::Event::Cut);
 					}
 					if let tetra::input::Key::V = key {
-						self.raw_input
-							.events
-							.push(egui::Event::Text(ClipboardContext::new()?.get_contents()?));
+						self.raw_input.events.push(egui::Event::Text(
+							ClipboardContext::new()
+								.map_err(logged)?
+								.get_contents()
+								.map_err(logged)?,
+						));
 					}
 				}
 
-				if let Some(key) = tetra_key_to_egui_key(*key) {
+				if let Some(key) = Option::<egui::Key>::from_tetra(*key) {
 					self.raw_input.events.push(egui::Event::Key {
 						key,
 						pressed: true,
@@ -421,20 +704,8 @@ impl EguiWrapper {
 				}
 			}
 			tetra::Event::KeyReleased { key } => {
-				match key {
-					tetra::input::Key::LeftCtrl | tetra::input::Key::RightCtrl => {
-						self.raw_input.modifiers.ctrl = false;
-						self.raw_input.modifiers.command = false;
-					}
-					tetra::input::Key::LeftShift | tetra::input::Key::RightShift => {
-						self.raw_input.modifiers.shift = false;
-					}
-					tetra::input::Key::LeftAlt | tetra::input::Key::RightAlt => {
-						self.raw_input.modifiers.alt = false;
-					}
-					_ => {}
-				}
-				if let Some(key) = tetra_key_to_egui_key(*key) {
+				self.update_modifiers(event);
+				if let Some(key) = Option::<egui::Key>::from_tetra(*key) {
 					self.raw_input.events.push(egui::Event::Key {
 						key,
 						pressed: false,
@@ -443,9 +714,9 @@ impl EguiWrapper {
 				}
 			}
 			tetra::Event::MouseButtonPressed { button } => {
-				if let Some(button) = tetra_mouse_button_to_egui_pointer_button(*button) {
+				if let Some(button) = Option::<egui::PointerButton>::from_tetra(*button) {
 					self.raw_input.events.push(egui::Event::PointerButton {
-						pos: tetra_vec2_to_egui_pos2(tetra::input::get_mouse_position(ctx)),
+						pos: self.window_to_gui(self.pointer_position(ctx)),
 						button,
 						pressed: true,
 						modifiers: self.raw_input.modifiers,
@@ -453,9 +724,9 @@ impl EguiWrapper {
 				}
 			}
 			tetra::Event::MouseButtonReleased { button } => {
-				if let Some(button) = tetra_mouse_button_to_egui_pointer_button(*button) {
+				if let Some(button) = Option::<egui::PointerButton>::from_tetra(*button) {
 					self.raw_input.events.push(egui::Event::PointerButton {
-						pos: tetra_vec2_to_egui_pos2(tetra::input::get_mouse_position(ctx)),
+						pos: self.window_to_gui(self.pointer_position(ctx)),
 						button,
 						pressed: false,
 						modifiers: self.raw_input.modifiers,
@@ -463,16 +734,13 @@ impl EguiWrapper {
 				}
 			}
 			tetra::Event::MouseMoved { position, .. } => {
+				self.pointer_position = Some(*position);
 				self.raw_input
 					.events
-					.push(egui::Event::PointerMoved(tetra_vec2_to_egui_pos2(
-						*position,
-					)));
+					.push(egui::Event::PointerMoved(self.window_to_gui(*position)));
 			}
 			tetra::Event::MouseWheelMoved { amount } => {
-				if tetra::input::is_key_down(ctx, tetra::input::Key::LeftCtrl)
-					|| tetra::input::is_key_down(ctx, tetra::input::Key::RightCtrl)
-				{
+				if self.raw_input.modifiers.ctrl {
 					self.raw_input
 						.events
 						.push(egui::Event::Zoom(ZOOM_SENSITIVITY.powi(amount.y)));
@@ -490,49 +758,143 @@ impl EguiWrapper {
 		Ok(())
 	}
 
+	/// Updates the held modifiers if `event` is the press or release
+	/// of a modifier key.
+	pub(crate) fn update_modifiers(&mut self, event: &tetra::Event) {
+		let (key, pressed) = match event {
+			tetra::Event::KeyPressed { key } => (key, true),
+			tetra::Event::KeyReleased { key } => (key, false),
+			_ => return,
+		};
+		let modifiers = &mut self.raw_input.modifiers;
+		match key {
+			tetra::input::Key::LeftCtrl | tetra::input::Key::RightCtrl => {
+				modifiers.ctrl = pressed;
+				modifiers.command = pressed;
+			}
+			tetra::input::Key::LeftShift | tetra::input::Key::RightShift => {
+				modifiers.shift = pressed;
+			}
+			tetra::input::Key::LeftAlt | tetra::input::Key::RightAlt => {
+				modifiers.alt = pressed;
+			}
+			_ => {}
+		}
+	}
+
+	/// Returns the mouse position from the latest mouse movement event,
+	/// which may have been replayed from an input recording or sent by
+	/// a [`UiScript`] rather than the real mouse.
+	fn pointer_position(&self, ctx: &tetra::Context) -> Vec2<f32> {
+		self.pointer_position
+			.unwrap_or_else(|| tetra::input::get_mouse_position(ctx))
+	}
+
+	/// Loads the egui context's memory (such as window positions)
+	/// from a file.
+	#[cfg(feature = "persistence")]
+	pub fn load_memory(&mut self, path: impl AsRef<std::path::Path>) -> Result<(), Error> {
+		let memory = std::fs::read_to_string(path)
+			.map_err(|error| logged(Error::PersistenceError(Box::new(error))))?;
+		*self.ctx.memory() = ron::from_str(&memory)
+			.map_err(|error| logged(Error::PersistenceError(Box::new(error))))?;
+		Ok(())
+	}
+
+	/// Saves the egui context's memory (such as window positions)
+	/// to a file.
+	#[cfg(feature = "persistence")]
+	pub fn save_memory(&self, path: impl AsRef<std::path::Path>) -> Result<(), Error> {
+		let memory = ron::to_string(&*self.ctx.memory())
+			.map_err(|error| logged(Error::PersistenceError(Box::new(error))))?;
+		std::fs::write(path, memory)
+			.map_err(|error| logged(Error::PersistenceError(Box::new(error))))
+	}
+
 	/// Begins a new GUI frame.
 	pub fn begin_frame(&mut self, ctx: &mut tetra::Context) -> Result<(), Error> {
+		if !UI_ENABLED {
+			return Ok(());
+		}
 		let now = Instant::now();
-		self.raw_input.screen_rect = Some(egui::Rect {
-			min: egui::pos2(0.0, 0.0),
-			max: egui::pos2(
+		let screen_size = self.screen_size.unwrap_or_else(|| {
+			Vec2::new(
 				tetra::window::get_width(ctx) as f32,
 				tetra::window::get_height(ctx) as f32,
-			),
+			)
+		});
+		self.raw_input.screen_rect = Some(egui::Rect {
+			min: egui::pos2(0.0, 0.0),
+			max: egui::pos2(screen_size.x, screen_size.y),
 		});
 		self.raw_input.predicted_dt = (now - self.last_frame_time).as_secs_f32();
 		self.last_frame_time = now;
 		self.meshes.clear();
 		self.ctx.begin_frame(self.raw_input.take());
-		if self.texture.is_none() {
-			self.texture = Some(egui_font_image_to_tetra_texture(
-				ctx,
-				self.ctx.font_image(),
-			)?);
+		// the previous frame has been drawn by now, so its stats
+		// are complete
+		self.ctx
+			.memory()
+			.data
+			.insert_temp(FrameStats::id(), self.stats);
+		self.stats = FrameStats::default();
+		mark_layer_orders(&self.ctx);
+		let font_image = self.ctx.font_image();
+		if self.texture.is_none() || self.texture_version != font_image.version {
+			self.texture_version = font_image.version;
+			self.texture = Some(egui_font_image_to_tetra_texture(ctx, font_image)?);
+			self.stats.buffer_uploads += 1;
 		}
 		Ok(())
 	}
 
 	/// Ends a GUI frame.
 	pub fn end_frame(&mut self, ctx: &mut tetra::Context) -> Result<(), Error> {
+		if !UI_ENABLED {
+			return Ok(());
+		}
+		self.stats.ui_time = self.last_frame_time.elapsed();
 		let (output, shapes) = self.ctx.end_frame();
-		if let Some(texture) = &self.texture {
-			let clipped_meshes = self.ctx.tessellate(shapes);
-			for ClippedMesh(rect, mesh) in clipped_meshes {
-				let rect = egui_rect_to_tetra_rectangle(rect);
-				let mesh = egui_mesh_to_tetra_mesh(ctx, mesh, texture.clone())?;
-				self.meshes.push((rect, mesh));
+		self.record_frame(&shapes);
+		if let Some(font_texture) = &self.texture {
+			for (order, shapes) in group_shapes_by_order(shapes) {
+				let tessellation_start = Instant::now();
+				let clipped_meshes = self.ctx.tessellate(shapes);
+				self.stats.tessellation_time += tessellation_start.elapsed();
+				for ClippedMesh(rect, mesh) in clipped_meshes {
+					let (texture, premultiplied) = match mesh.texture_id {
+						egui::TextureId::Egui => (font_texture, true),
+						egui::TextureId::User(id) => match self.user_textures.get(&id) {
+							Some(texture) => (texture, false),
+							None => continue,
+						},
+					};
+					self.stats.mesh_count += 1;
+					self.stats.vertex_count += mesh.vertices.len();
+					self.stats.index_count += mesh.indices.len();
+					// each mesh gets a new vertex buffer and index buffer
+					self.stats.buffer_uploads += 2;
+					self.meshes.push(GuiMesh {
+						order,
+						clip_rect: tetra::graphics::Rectangle::from_egui(rect),
+						mesh: egui_mesh_to_tetra_mesh(ctx, mesh, texture.clone())?,
+						premultiplied,
+					});
+				}
 			}
 		}
 
 		// open URLs that were clicked
 		if let Some(open_url) = &output.open_url {
-			open::that(&open_url.url)?;
+			open::that(&open_url.url).map_err(logged)?;
 		}
 
 		// copy text to clipboard
 		if !output.copied_text.is_empty() {
-			ClipboardContext::new()?.set_contents(output.copied_text)?;
+			ClipboardContext::new()
+				.map_err(logged)?
+				.set_contents(output.copied_text)
+				.map_err(logged)?;
 		}
 
 		Ok(())
@@ -541,15 +903,66 @@ impl EguiWrapper {
 	/// Draws the latest finished GUI frame to the screen.
 	///
 	/// Note that this function changes the Tetra blend mode and
-	/// scissor state.
+	/// scissor state, and if a [transform](Self::set_transform) is set,
+	/// the transform matrix.
 	pub fn draw_frame(&mut self, ctx: &mut tetra::Context) {
-		graphics::set_blend_mode(ctx, BlendMode::Alpha(BlendAlphaMode::Premultiplied));
-		for (rect, mesh) in &self.meshes {
-			graphics::set_scissor(ctx, *rect);
-			mesh.draw(ctx, tetra::math::Vec2::zero());
+		self.draw_layers(ctx, ..);
+	}
+
+	/// Draws the layers of the latest finished GUI frame whose
+	/// [`Order`] is within `orders` to the screen.
+	///
+	/// This can be used to draw game content in between layers
+	/// of the GUI. For example, to draw the game above egui's
+	/// background layer (which contains panels) but below everything
+	/// else:
+	///
+	/// ```no_run
+	/// # fn draw(ctx: &mut tetra::Context, egui: &mut egui_tetra::EguiWrapper) {
+	/// use egui_tetra::egui::Order;
+	///
+	/// egui.draw_layers(ctx, ..=Order::Background);
+	/// // draw the game here
+	/// egui.draw_layers(ctx, Order::PanelResizeLine..);
+	/// # }
+	/// ```
+	///
+	/// Note that this function changes the Tetra blend mode and
+	/// scissor state, and if a [transform](Self::set_transform) is set,
+	/// the transform matrix.
+	pub fn draw_layers(&mut self, ctx: &mut tetra::Context, orders: impl RangeBounds<Order>) {
+		if !UI_ENABLED {
+			return;
+		}
+		let draw_start = Instant::now();
+		if let Some(transform) = self.transform {
+			graphics::set_transform_matrix(ctx, transform);
+		}
+		for gui_mesh in &self.meshes {
+			if !orders.contains(&gui_mesh.order) {
+				continue;
+			}
+			graphics::set_blend_mode(
+				ctx,
+				BlendMode::Alpha(if gui_mesh.premultiplied {
+					BlendAlphaMode::Premultiplied
+				} else {
+					BlendAlphaMode::Multiply
+				}),
+			);
+			let clip_rect = match self.transform {
+				Some(transform) => transform_rectangle(transform, gui_mesh.clip_rect),
+				None => gui_mesh.clip_rect,
+			};
+			graphics::set_scissor(ctx, clip_rect);
+			gui_mesh.mesh.draw(ctx, tetra::math::Vec2::zero());
 		}
 		graphics::reset_scissor(ctx);
 		graphics::reset_blend_mode(ctx);
+		if self.transform.is_some() {
+			graphics::reset_transform_matrix(ctx);
+		}
+		self.stats.draw_time += draw_start.elapsed();
 	}
 }
 
@@ -559,137 +972,67 @@ impl Default for EguiWrapper {
 	}
 }
 
-/// A trait analogous to [`tetra::State`], but with the addition of a
-/// [`ui`](State::ui) callback and an `egui_ctx` argument in the
-/// other callbacks.
-///
-/// You can use a type implementing this trait as your main game
-/// state by wrapping it with a [`StateWrapper`] and passing the wrapper
-/// to [`tetra::Context::run`].
-#[allow(unused_variables)]
-pub trait State<E: From<Error> = Error> {
-	/// Called when it is time for the game to construct a GUI.
-	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
-		Ok(())
-	}
-
-	/// Called when it is time for the game to update.
-	fn update(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
-		Ok(())
-	}
-
-	/// Called when it is time for the game to be drawn.
-	fn draw(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
-		Ok(())
-	}
-
-	/// Called when a window or input event occurs.
-	///
-	/// Mouse and keyboard input events will not be received if the GUI
-	/// is using the mouse or keyboard, respectively.
-	fn event(
-		&mut self,
-		ctx: &mut tetra::Context,
-		egui_ctx: &egui::CtxRef,
-		event: Event,
-	) -> Result<(), E> {
-		Ok(())
-	}
-}
-
-/// An adaptor that implements [`tetra::State`] for implementors of
-/// [`State`].
-pub struct StateWrapper<E: From<Error>> {
-	events: Vec<tetra::Event>,
-	state: Box<dyn State<E>>,
-	egui: EguiWrapper,
-}
+#[cfg(test)]
+mod tests {
+	use super::*;
 
-impl<E: From<Error>> StateWrapper<E> {
-	/// Wraps an implementor of [`State`] so it implements [`tetra::State`].
-	pub fn new(state: impl State<E> + 'static) -> Self {
-		Self {
-			events: vec![],
-			state: Box::new(state),
-			egui: EguiWrapper::new(),
+	/// Returns the index of an order in [`ORDERS`], failing to compile
+	/// if egui adds an order that isn't in the list.
+	fn order_index(order: Order) -> usize {
+		match order {
+			Order::Background => 0,
+			Order::PanelResizeLine => 1,
+			Order::Middle => 2,
+			Order::Foreground => 3,
+			Order::Tooltip => 4,
+			Order::Debug => 5,
 		}
 	}
 
-	/// Returns a reference to this wrapper's egui context.
-	pub fn ctx(&self) -> &egui::CtxRef {
-		self.egui.ctx()
+	#[test]
+	fn orders_match_egui() {
+		for (i, order) in ORDERS.iter().enumerate() {
+			assert_eq!(order_index(*order), i);
+		}
+		assert!(ORDERS.windows(2).all(|pair| pair[0] < pair[1]));
 	}
-}
-
-/*
-A note about the order of events:
-
-Tetra's game loop is:
-- Poll and dispatch events
-- Run the update callback (may not happen every frame depending
-on the timestamp setting)
-- Run the draw callback
-
-We don't want to dispatch mouse and keyboard events to the
-gameplay code if egui wants them, but egui can't tell us if
-it wants them until after we call end_frame(). So we need
-to run the UI code at the beginning of the game loop (but
-not draw the result until the end of the game loop, of course).
-
-Here's the order of operations I settled on:
-- Whenever event is called, send it to the egui ctx and queue
-it up for later
-- At the beginning of update, run the UI callback and save the
-resulting meshes and scissor rectangles. Then, dispatch queued
-events to the gameplay code (unless the UI wanted them).
-- In the draw callback, draw gameplay first, then UI
-*/
 
-impl<E: From<Error>> tetra::State<E> for StateWrapper<E> {
-	fn update(&mut self, ctx: &mut tetra::Context) -> Result<(), E> {
-		self.egui.begin_frame(ctx)?;
-		self.state.ui(ctx, self.egui.ctx())?;
-		self.egui.end_frame(ctx)?;
-
-		for event in self.events.drain(..) {
-			match &event {
-				Event::KeyPressed { .. } | Event::KeyReleased { .. } => {
-					if self.egui.ctx().wants_keyboard_input() {
-						continue;
-					}
-				}
-				Event::MouseButtonPressed { .. } | Event::MouseButtonReleased { .. } => {
-					if self.egui.ctx().is_using_pointer() {
-						continue;
-					}
-				}
-				Event::MouseMoved { .. } => {
-					if self.egui.ctx().is_using_pointer() {
-						continue;
-					}
-				}
-				Event::MouseWheelMoved { .. } => {
-					if self.egui.ctx().is_using_pointer() {
-						continue;
-					}
+	#[test]
+	fn layer_order_groups() {
+		let mut ctx = CtxRef::default();
+		let color = |i: usize| egui::Color32::from_gray(i as u8 + 1);
+		for _ in 0..2 {
+			ctx.begin_frame(egui::RawInput::default());
+			mark_layer_orders(&ctx);
+			// areas are shown from front to back, so the output only
+			// ends up in order if egui sorts it
+			for (i, order) in ORDERS.iter().enumerate().rev() {
+				egui::Area::new(egui::Id::new("test_area").with(i))
+					.order(*order)
+					.show(&ctx, |ui| {
+						ui.painter().rect_filled(
+							egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(10.0, 10.0)),
+							0.0,
+							color(i),
+						);
+					});
+			}
+			let (_, shapes) = ctx.end_frame();
+			let groups = group_shapes_by_order(shapes);
+			// the first group holds any shapes before the first marker
+			assert!(groups[0].1.is_empty());
+			let orders = groups[1..]
+				.iter()
+				.map(|(order, _)| *order)
+				.collect::<Vec<_>>();
+			assert_eq!(orders, ORDERS);
+			for (i, (_, group)) in groups[1..].iter().enumerate() {
+				assert_eq!(group.len(), 1);
+				match &group[0].1 {
+					egui::Shape::Rect(rect) => assert_eq!(rect.fill, color(i)),
+					shape => panic!("expected a rectangle, got {:?}", shape),
 				}
-				_ => {}
 			}
-			self.state.event(ctx, self.egui.ctx(), event)?;
 		}
-
-		self.state.update(ctx, self.egui.ctx())
-	}
-
-	fn draw(&mut self, ctx: &mut tetra::Context) -> Result<(), E> {
-		self.state.draw(ctx, self.egui.ctx())?;
-		self.egui.draw_frame(ctx);
-		Ok(())
-	}
-
-	fn event(&mut self, ctx: &mut tetra::Context, event: Event) -> Result<(), E> {
-		self.egui.event(ctx, &event)?;
-		self.events.push(event);
-		Ok(())
 	}
 }
@@ -0,0 +1,211 @@
+use std::{
+	collections::VecDeque,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::Instant,
+};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::Error;
+
+const DEFAULT_CAPACITY: usize = 1000;
+
+/// A message captured by a [`Logger`].
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+	/// The level of the message.
+	pub level: Level,
+	/// The target of the message, usually the module it was logged from.
+	pub target: String,
+	/// The text of the message.
+	pub message: String,
+	/// When the message was logged.
+	pub time: Instant,
+}
+
+/// A ring buffer of the most recent messages captured by a [`Logger`].
+///
+/// Clones of a [`LogBuffer`] share the same messages, so the buffer
+/// can be read from a [`LogPanel`](crate::LogPanel) while the logger
+/// writes to it.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+	records: Arc<Mutex<VecDeque<LogRecord>>>,
+	capacity: usize,
+}
+
+impl LogBuffer {
+	/// Creates a new, empty [`LogBuffer`] that keeps up to `capacity`
+	/// messages.
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			records: Arc::new(Mutex::new(VecDeque::new())),
+			capacity: capacity.max(1),
+		}
+	}
+
+	/// Returns a copy of the messages in the buffer, from oldest
+	/// to newest.
+	pub fn records(&self) -> Vec<LogRecord> {
+		self.lock().iter().cloned().collect()
+	}
+
+	/// Returns the number of messages in the buffer.
+	pub fn len(&self) -> usize {
+		self.lock().len()
+	}
+
+	/// Returns `true` if the buffer has no messages.
+	pub fn is_empty(&self) -> bool {
+		self.lock().is_empty()
+	}
+
+	/// Returns how many messages the buffer keeps.
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Adds a message to the buffer, removing the oldest message
+	/// if the buffer is full.
+	pub fn push(&self, record: LogRecord) {
+		let mut records = self.lock();
+		records.push_back(record);
+		while records.len() > self.capacity {
+			records.pop_front();
+		}
+	}
+
+	/// Removes all messages from the buffer.
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+
+	fn lock(&self) -> MutexGuard<'_, VecDeque<LogRecord>> {
+		// a panic while holding the lock can't leave the records
+		// in an invalid state, so poisoning is ignored
+		self.records.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+impl Default for LogBuffer {
+	fn default() -> Self {
+		Self::new(DEFAULT_CAPACITY)
+	}
+}
+
+/// A logger for the [`log`] crate that keeps recent messages in a
+/// [`LogBuffer`], so they can be shown in-game with a
+/// [`LogPanel`](crate::LogPanel).
+///
+/// Messages are also printed to stderr by default, so they still show
+/// up in the terminal. This requires the `log` feature.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # struct MainState;
+/// # impl egui_tetra::State<Box<dyn Error>> for MainState {}
+/// use egui_tetra::{LogPanel, Logger, StateWrapper};
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// 	let logs = Logger::new()
+/// 		.level(log::LevelFilter::Debug)
+/// 		.init()?;
+/// 	log::info!("starting up");
+/// 	tetra::ContextBuilder::new("example", 800, 600)
+/// 		.build()?
+/// 		.run(|_| Ok(StateWrapper::new(MainState).with_plugin(LogPanel::new(logs))))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Logger {
+	buffer: LogBuffer,
+	level: LevelFilter,
+	echo: bool,
+}
+
+impl Logger {
+	/// Creates a new [`Logger`] that keeps the last 1000 messages of
+	/// level `Info` and above.
+	pub fn new() -> Self {
+		Self {
+			buffer: LogBuffer::default(),
+			level: LevelFilter::Info,
+			echo: true,
+		}
+	}
+
+	/// Sets how many messages to keep and returns the logger.
+	///
+	/// Defaults to 1000.
+	pub fn capacity(mut self, capacity: usize) -> Self {
+		self.buffer = LogBuffer::new(capacity);
+		self
+	}
+
+	/// Sets the most verbose level of messages to capture and returns
+	/// the logger.
+	///
+	/// Defaults to [`LevelFilter::Info`].
+	pub fn level(mut self, level: LevelFilter) -> Self {
+		self.level = level;
+		self
+	}
+
+	/// Sets whether messages are also printed to stderr and returns
+	/// the logger.
+	///
+	/// Defaults to `true`.
+	pub fn echo(mut self, echo: bool) -> Self {
+		self.echo = echo;
+		self
+	}
+
+	/// Returns the buffer the logger writes messages to.
+	pub fn buffer(&self) -> LogBuffer {
+		self.buffer.clone()
+	}
+
+	/// Installs the logger as the global logger for the [`log`] crate
+	/// and returns its buffer.
+	///
+	/// Returns [`Error::LoggerError`] if a global logger has already
+	/// been installed.
+	pub fn init(self) -> Result<LogBuffer, Error> {
+		let buffer = self.buffer();
+		let level = self.level;
+		log::set_boxed_logger(Box::new(self))
+			.map_err(|error| Error::LoggerError(Box::new(error)))?;
+		log::set_max_level(level);
+		Ok(buffer)
+	}
+}
+
+impl Default for Logger {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Log for Logger {
+	fn enabled(&self, metadata: &Metadata) -> bool {
+		metadata.level() <= self.level
+	}
+
+	fn log(&self, record: &Record) {
+		if !self.enabled(record.metadata()) {
+			return;
+		}
+		let message = record.args().to_string();
+		if self.echo {
+			eprintln!("[{} {}] {}", record.level(), record.target(), message);
+		}
+		self.buffer.push(LogRecord {
+			level: record.level(),
+			target: record.target().to_string(),
+			message,
+			time: Instant::now(),
+		});
+	}
+
+	fn flush(&self) {}
+}
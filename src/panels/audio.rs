@@ -0,0 +1,236 @@
+use tetra::audio::{self, SoundInstance, SoundState};
+
+/// A [`SoundInstance`] registered with a [`SoundRegistry`], along with
+/// the settings it was last given through the registry.
+///
+/// Tetra can't report a sound instance's volume, speed, or whether it's
+/// repeating, so changes to these should go through this type rather than
+/// the [`SoundInstance`] for the registry to show the right values.
+pub struct RegisteredSound {
+	name: String,
+	instance: SoundInstance,
+	volume: f32,
+	speed: f32,
+	repeating: bool,
+}
+
+impl RegisteredSound {
+	/// Returns the name the sound was registered with.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the sound instance.
+	pub fn instance(&self) -> &SoundInstance {
+		&self.instance
+	}
+
+	/// Returns the volume of the sound.
+	pub fn volume(&self) -> f32 {
+		self.volume
+	}
+
+	/// Sets the volume of the sound.
+	pub fn set_volume(&mut self, volume: f32) {
+		self.volume = volume;
+		self.instance.set_volume(volume);
+	}
+
+	/// Returns the playback speed of the sound.
+	pub fn speed(&self) -> f32 {
+		self.speed
+	}
+
+	/// Sets the playback speed of the sound.
+	pub fn set_speed(&mut self, speed: f32) {
+		self.speed = speed;
+		self.instance.set_speed(speed);
+	}
+
+	/// Returns `true` if the sound repeats when it finishes.
+	pub fn is_repeating(&self) -> bool {
+		self.repeating
+	}
+
+	/// Sets whether the sound repeats when it finishes.
+	pub fn set_repeating(&mut self, repeating: bool) {
+		self.repeating = repeating;
+		self.instance.set_repeating(repeating);
+	}
+
+	fn controls_ui(&mut self, ui: &mut egui::Ui) {
+		let state = self.instance.state();
+		ui.label(&self.name);
+		ui.label(match state {
+			SoundState::Playing => "Playing",
+			SoundState::Paused => "Paused",
+			SoundState::Stopped => "Stopped",
+		});
+		ui.horizontal(|ui| {
+			if state == SoundState::Playing {
+				if ui.button("Pause").clicked() {
+					self.instance.pause();
+				}
+			} else if ui.button("Play").clicked() {
+				self.instance.play();
+			}
+			if ui
+				.add_enabled(state != SoundState::Stopped, egui::Button::new("Stop"))
+				.clicked()
+			{
+				self.instance.stop();
+			}
+		});
+		let mut volume = self.volume;
+		if ui.add(egui::Slider::new(&mut volume, 0.0..=2.0)).changed() {
+			self.set_volume(volume);
+		}
+		let mut speed = self.speed;
+		if ui
+			.add(egui::Slider::new(&mut speed, 0.1..=4.0).logarithmic(true))
+			.changed()
+		{
+			self.set_speed(speed);
+		}
+		let mut repeating = self.repeating;
+		if ui.checkbox(&mut repeating, "").changed() {
+			self.set_repeating(repeating);
+		}
+		ui.end_row();
+	}
+}
+
+/// A collection of named [`SoundInstance`]s that can be shown and
+/// edited with an audio mixer widget.
+///
+/// ```
+/// # fn ui(ctx: &mut tetra::Context, egui_ctx: &egui_tetra::egui::CtxRef, sounds: &mut egui_tetra::SoundRegistry) {
+/// use egui_tetra::egui;
+///
+/// egui::Window::new("Audio").show(egui_ctx, |ui| {
+/// 	sounds.ui(ctx, ui);
+/// });
+/// # }
+/// ```
+///
+/// This type is only available with the `audio` feature.
+pub struct SoundRegistry {
+	sounds: Vec<RegisteredSound>,
+	id_source: egui::Id,
+}
+
+impl SoundRegistry {
+	/// Creates a new, empty [`SoundRegistry`].
+	pub fn new() -> Self {
+		Self {
+			sounds: vec![],
+			id_source: egui::Id::new("egui_tetra_sound_registry"),
+		}
+	}
+
+	/// Sets a source for the mixer widget's ID and returns the registry.
+	///
+	/// This is needed when showing more than one [`SoundRegistry`]
+	/// in the same [`Ui`](egui::Ui).
+	pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+		self.id_source = egui::Id::new(id_source);
+		self
+	}
+
+	/// Adds a sound instance to the registry and returns it.
+	///
+	/// The instance is assumed to have the default volume and speed
+	/// of `1.0` and to not be repeating. If an instance with the same
+	/// name is already registered, it is replaced.
+	pub fn register(
+		&mut self,
+		name: impl Into<String>,
+		instance: SoundInstance,
+	) -> &mut RegisteredSound {
+		let sound = RegisteredSound {
+			name: name.into(),
+			instance,
+			volume: 1.0,
+			speed: 1.0,
+			repeating: false,
+		};
+		let index = match self
+			.sounds
+			.iter()
+			.position(|existing| existing.name == sound.name)
+		{
+			Some(index) => {
+				self.sounds[index] = sound;
+				index
+			}
+			None => {
+				self.sounds.push(sound);
+				self.sounds.len() - 1
+			}
+		};
+		&mut self.sounds[index]
+	}
+
+	/// Removes the sound with the given name from the registry and
+	/// returns its instance.
+	///
+	/// This doesn't stop the sound.
+	pub fn unregister(&mut self, name: &str) -> Option<SoundInstance> {
+		let index = self.sounds.iter().position(|sound| sound.name == name)?;
+		Some(self.sounds.remove(index).instance)
+	}
+
+	/// Returns the sound with the given name, if it's registered.
+	pub fn get(&self, name: &str) -> Option<&RegisteredSound> {
+		self.sounds.iter().find(|sound| sound.name == name)
+	}
+
+	/// Returns a mutable reference to the sound with the given name,
+	/// if it's registered.
+	pub fn get_mut(&mut self, name: &str) -> Option<&mut RegisteredSound> {
+		self.sounds.iter_mut().find(|sound| sound.name == name)
+	}
+
+	/// Returns an iterator over the registered sounds, in the order
+	/// they were registered.
+	pub fn iter(&self) -> impl Iterator<Item = &RegisteredSound> {
+		self.sounds.iter()
+	}
+
+	/// Shows a mixer with the master volume and controls for every
+	/// registered sound.
+	pub fn ui(&mut self, ctx: &mut tetra::Context, ui: &mut egui::Ui) {
+		let mut master_volume = audio::get_master_volume(ctx);
+		ui.horizontal(|ui| {
+			ui.label("Master volume");
+			if ui
+				.add(egui::Slider::new(&mut master_volume, 0.0..=2.0))
+				.changed()
+			{
+				audio::set_master_volume(ctx, master_volume);
+			}
+		});
+		ui.separator();
+		if self.sounds.is_empty() {
+			ui.label(egui::RichText::new("No sounds registered").weak());
+			return;
+		}
+		egui::Grid::new(self.id_source)
+			.striped(true)
+			.show(ui, |ui| {
+				for heading in ["Sound", "State", "", "Volume", "Speed", "Repeat"] {
+					ui.strong(heading);
+				}
+				ui.end_row();
+				for sound in &mut self.sounds {
+					sound.controls_ui(ui);
+				}
+			});
+	}
+}
+
+impl Default for SoundRegistry {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,427 @@
+use std::collections::VecDeque;
+
+use egui::epaint::text::cursor::CCursor;
+use tetra::{input::Key, Event};
+
+use crate::{CommandRegistry, Error, EventDelivery, EventRecord, Plugin};
+
+const DEFAULT_MAX_LINES: usize = 1000;
+const MAX_HISTORY: usize = 100;
+/// Commands handled by the console itself, along with their usage
+/// and help text.
+const BUILTIN_COMMANDS: [(&str, &str, &str); 2] = [
+	("clear", "clear", "Clears the console's output."),
+	(
+		"help",
+		"help [command: string]",
+		"Lists the commands, or shows how to use a command.",
+	),
+];
+
+/// What kind of text a line in the console's output is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+	Input,
+	Output,
+	Error,
+}
+
+/// A [`Plugin`] that shows a drop-down console for running commands
+/// from a [`CommandRegistry`].
+///
+/// The console supports:
+/// - Browsing previously run commands with the up and down arrow keys
+/// - Completing command names with Tab
+/// - `help` and `help <command>` for listing commands and showing
+///   their arguments and help text
+/// - `clear` for clearing the output
+///
+/// Registered commands named `help` or `clear` are shadowed by the
+/// built-in commands, so they can't be run from the console.
+///
+/// The console is opened and closed with a toggle key, which defaults to
+/// the backquote (`` ` ``) key. Presses of the toggle key are consumed by
+/// the plugin, so they aren't sent to the game. While the console is open,
+/// it keeps keyboard focus (unless another widget takes it), so keyboard
+/// input goes to the console rather than the game.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # struct MainState;
+/// # impl egui_tetra::State<Box<dyn Error>> for MainState {}
+/// use egui_tetra::{Command, CommandRegistry, ConsolePanel, StateWrapper};
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// 	let mut commands = CommandRegistry::new();
+/// 	commands.register(Command::new("quit", "Quits the game."), |ctx, _, _| {
+/// 		tetra::window::quit(ctx);
+/// 		Ok(String::new())
+/// 	});
+/// 	tetra::ContextBuilder::new("example", 800, 600)
+/// 		.build()?
+/// 		.run(|_| Ok(StateWrapper::new(MainState).with_plugin(ConsolePanel::new(commands))))
+/// }
+/// ```
+pub struct ConsolePanel {
+	commands: CommandRegistry,
+	open: bool,
+	toggle_key: Key,
+	height: f32,
+	max_lines: usize,
+	lines: VecDeque<(LineKind, String)>,
+	input: String,
+	/// Whether the console was closed by a press of the toggle key
+	/// this frame, so the text typed by that press should be removed.
+	closed_by_toggle_key: bool,
+	history: Vec<String>,
+	/// The index into `history` being browsed, if any.
+	history_index: Option<usize>,
+}
+
+impl ConsolePanel {
+	/// Creates a new [`ConsolePanel`] that runs commands from the
+	/// given registry.
+	pub fn new(commands: CommandRegistry) -> Self {
+		Self {
+			commands,
+			open: false,
+			toggle_key: Key::Backquote,
+			height: 300.0,
+			max_lines: DEFAULT_MAX_LINES,
+			lines: VecDeque::new(),
+			input: String::new(),
+			closed_by_toggle_key: false,
+			history: vec![],
+			history_index: None,
+		}
+	}
+
+	/// Sets whether the console is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Sets the key that opens and closes the console and returns
+	/// the panel.
+	pub fn toggle_key(mut self, key: Key) -> Self {
+		self.toggle_key = key;
+		self
+	}
+
+	/// Sets the height of the console (in points) and returns the panel.
+	pub fn height(mut self, height: f32) -> Self {
+		self.height = height;
+		self
+	}
+
+	/// Sets how many lines of output to keep and returns the panel.
+	///
+	/// Defaults to 1000.
+	pub fn max_lines(mut self, max_lines: usize) -> Self {
+		self.max_lines = max_lines;
+		self
+	}
+
+	/// Returns the registry the console runs commands from.
+	pub fn commands(&self) -> &CommandRegistry {
+		&self.commands
+	}
+
+	/// Returns the registry the console runs commands from mutably.
+	pub fn commands_mut(&mut self) -> &mut CommandRegistry {
+		&mut self.commands
+	}
+
+	/// Adds a line of text to the console's output.
+	pub fn print(&mut self, text: impl Into<String>) {
+		self.push_line(LineKind::Output, text.into());
+	}
+
+	fn push_line(&mut self, kind: LineKind, text: String) {
+		for line in text.lines() {
+			self.lines.push_back((kind, line.to_string()));
+		}
+		while self.lines.len() > self.max_lines {
+			self.lines.pop_front();
+		}
+	}
+
+	fn input_id() -> egui::Id {
+		egui::Id::new("egui_tetra_console_input")
+	}
+
+	fn set_open(&mut self, egui_ctx: &egui::CtxRef, open: bool) {
+		self.open = open;
+		if !open {
+			egui_ctx.memory().surrender_focus(Self::input_id());
+		}
+	}
+
+	fn submit(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) {
+		let line = std::mem::take(&mut self.input);
+		self.history_index = None;
+		if line.trim().is_empty() {
+			return;
+		}
+		if self.history.last() != Some(&line) {
+			self.history.push(line.clone());
+			if self.history.len() > MAX_HISTORY {
+				self.history.remove(0);
+			}
+		}
+		self.push_line(LineKind::Input, format!("> {}", line));
+		let words: Vec<&str> = line.split_whitespace().collect();
+		match words.as_slice() {
+			["clear"] => self.lines.clear(),
+			["help"] => {
+				let help = self
+					.commands
+					.commands()
+					.filter(|command| builtin_command(command.name()).is_none())
+					.map(|command| format!("{} - {}", command.name(), command.help()))
+					.chain(
+						BUILTIN_COMMANDS
+							.iter()
+							.map(|(name, _, help)| format!("{} - {}", name, help)),
+					)
+					.collect::<Vec<_>>()
+					.join("\n");
+				self.push_line(LineKind::Output, help);
+			}
+			["help", name] => {
+				let help = match (builtin_command(name), self.commands.get(name)) {
+					(Some((_, usage, help)), _) => format!("usage: {}\n{}", usage, help),
+					(None, Some(command)) => {
+						format!("usage: {}\n{}", command.usage(), command.help())
+					}
+					(None, None) => {
+						self.push_line(LineKind::Error, format!("unknown command `{}`", name));
+						return;
+					}
+				};
+				self.push_line(LineKind::Output, help);
+			}
+			// the built-ins shadow registered commands, even when they're
+			// given the wrong arguments
+			_ => match words.first().and_then(|name| builtin_command(name)) {
+				Some((_, usage, _)) => self.push_line(LineKind::Error, format!("usage: {}", usage)),
+				None => match self.commands.run(ctx, egui_ctx, &line) {
+					Ok(output) => self.push_line(LineKind::Output, output),
+					Err(error) => self.push_line(LineKind::Error, error),
+				},
+			},
+		}
+	}
+
+	/// Moves to the next older or newer command in the history.
+	fn browse_history(&mut self, older: bool) {
+		if self.history.is_empty() {
+			return;
+		}
+		let index = match (self.history_index, older) {
+			(None, true) => Some(self.history.len() - 1),
+			(None, false) => None,
+			(Some(index), true) => Some(index.saturating_sub(1)),
+			(Some(index), false) if index + 1 < self.history.len() => Some(index + 1),
+			(Some(_), false) => None,
+		};
+		self.history_index = index;
+		self.input = match index {
+			Some(index) => self.history[index].clone(),
+			None => String::new(),
+		};
+	}
+
+	/// Completes the command name being typed, or lists the possible
+	/// completions if there's more than one.
+	fn complete(&mut self) {
+		// command names are completed at the start of the line and
+		// after `help`
+		let (prefix, partial) = match self.input.strip_prefix("help ") {
+			Some(rest) => ("help ", rest.trim_start()),
+			None => ("", self.input.trim_start()),
+		};
+		if partial.contains(char::is_whitespace) {
+			return;
+		}
+		let mut matches: Vec<String> = self
+			.commands
+			.complete(partial)
+			.into_iter()
+			.map(String::from)
+			.collect();
+		if prefix.is_empty() {
+			matches.extend(
+				BUILTIN_COMMANDS
+					.iter()
+					.map(|(name, _, _)| name.to_string())
+					.filter(|name| name.starts_with(partial)),
+			);
+			matches.sort_unstable();
+			matches.dedup();
+		}
+		let completed = match matches.as_slice() {
+			[] => return,
+			[name] => format!("{} ", name),
+			[first, rest @ ..] => {
+				let mut common = first.clone();
+				for name in rest {
+					while !name.starts_with(&common) {
+						common.pop();
+					}
+				}
+				let list = matches.join("  ");
+				self.push_line(LineKind::Output, list);
+				common
+			}
+		};
+		self.input = format!("{}{}", prefix, completed);
+	}
+
+	fn show(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) {
+		let screen_rect = egui_ctx.input().screen_rect();
+		let frame = egui::Frame {
+			corner_radius: 0.0,
+			..egui::Frame::window(&egui_ctx.style())
+		};
+		egui::Area::new("egui_tetra_console")
+			.order(egui::Order::Foreground)
+			.fixed_pos(screen_rect.min)
+			.show(egui_ctx, |ui| {
+				frame.show(ui, |ui| {
+					ui.set_width(screen_rect.width() - 2.0 * frame.margin.x);
+					ui.set_height(self.height);
+					let input_height = ui.spacing().interact_size.y + ui.spacing().item_spacing.y;
+					egui::ScrollArea::vertical()
+						.max_height(self.height - input_height)
+						.auto_shrink([false, false])
+						.stick_to_bottom()
+						.show(ui, |ui| {
+							for (kind, text) in &self.lines {
+								let text = egui::RichText::new(text).monospace();
+								ui.label(match kind {
+									LineKind::Input => text.weak(),
+									LineKind::Output => text,
+									LineKind::Error => text.color(egui::Color32::LIGHT_RED),
+								});
+							}
+						});
+					self.input_ui(ctx, egui_ctx, ui);
+				});
+			});
+	}
+
+	fn input_ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef, ui: &mut egui::Ui) {
+		let id = Self::input_id();
+		let (tab, up, down, enter) = {
+			let input = ui.input();
+			(
+				input.key_pressed(egui::Key::Tab),
+				input.key_pressed(egui::Key::ArrowUp),
+				input.key_pressed(egui::Key::ArrowDown),
+				input.key_pressed(egui::Key::Enter),
+			)
+		};
+		let had_focus = ui.memory().has_focus(id);
+		let mut input = std::mem::take(&mut self.input);
+		let response = ui.add(
+			egui::TextEdit::singleline(&mut input)
+				.id(id)
+				.text_style(egui::TextStyle::Monospace)
+				.desired_width(f32::INFINITY)
+				.lock_focus(true)
+				.hint_text("Type `help` for a list of commands"),
+		);
+		self.input = input;
+		let mut edited = false;
+		if response.lost_focus() && enter {
+			self.submit(ctx, egui_ctx);
+			edited = true;
+		} else if had_focus && tab {
+			self.complete();
+			edited = true;
+		} else if had_focus && (up || down) {
+			self.browse_history(up);
+			edited = true;
+		}
+		if edited {
+			// move the cursor to the end of the new text
+			if let Some(mut state) = egui::text_edit::TextEditState::load(egui_ctx, id) {
+				let end = CCursor::new(self.input.chars().count());
+				state.set_ccursor_range(Some(egui::text_edit::CCursorRange::one(end)));
+				state.store(egui_ctx, id);
+			}
+		}
+		// keep the keyboard unless another widget wants it
+		if egui_ctx.memory().focus().unwrap_or(id) == id || edited {
+			response.request_focus();
+		}
+	}
+}
+
+/// Returns the name, usage, and help text of the built-in command
+/// with the given name, if there is one.
+fn builtin_command(name: &str) -> Option<(&'static str, &'static str, &'static str)> {
+	BUILTIN_COMMANDS
+		.iter()
+		.copied()
+		.find(|(builtin, _, _)| *builtin == name)
+}
+
+impl<E: From<Error>> Plugin<E> for ConsolePanel {
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		self.closed_by_toggle_key = false;
+		if self.open {
+			self.show(ctx, egui_ctx);
+		}
+		Ok(())
+	}
+
+	fn event(
+		&mut self,
+		_ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		event: &Event,
+	) -> Result<bool, E> {
+		if let Event::KeyPressed { key } = event {
+			if *key == self.toggle_key {
+				self.set_open(egui_ctx, !self.open);
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
+
+	fn inspect_event(
+		&mut self,
+		_ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		record: &EventRecord,
+	) -> Result<(), E> {
+		match &record.event {
+			// while the console is typed in, the toggle key goes to the
+			// GUI instead of the plugin, so it's handled here
+			Event::KeyPressed { key }
+				if *key == self.toggle_key
+					&& record.delivery == EventDelivery::Gui
+					&& self.open && egui_ctx.memory().has_focus(Self::input_id()) =>
+			{
+				self.set_open(egui_ctx, false);
+				self.closed_by_toggle_key = true;
+			}
+			// the text input from the toggle key comes right after the
+			// press, and has already been typed into the console (its
+			// delivery doesn't matter, since closing the console gives up
+			// the keyboard)
+			Event::TextInput { text } if self.closed_by_toggle_key => {
+				if let Some(index) = self.input.rfind(text.as_str()) {
+					self.input.replace_range(index..index + text.len(), "");
+				}
+				self.closed_by_toggle_key = false;
+			}
+			_ => {}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,229 @@
+use std::{collections::VecDeque, time::Instant};
+
+use tetra::Event;
+
+use crate::{Error, EventDelivery, EventRecord, Plugin};
+
+const DEFAULT_MAX_RECORDS: usize = 500;
+
+/// A group of event types that can be shown or hidden in an
+/// [`EventLogPanel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+	Window,
+	Keyboard,
+	Text,
+	MouseButton,
+	MouseMotion,
+	MouseWheel,
+	Gamepad,
+	Other,
+}
+
+impl EventKind {
+	const ALL: [Self; 8] = [
+		Self::Window,
+		Self::Keyboard,
+		Self::Text,
+		Self::MouseButton,
+		Self::MouseMotion,
+		Self::MouseWheel,
+		Self::Gamepad,
+		Self::Other,
+	];
+
+	fn of(event: &Event) -> Self {
+		match event {
+			Event::Resized { .. }
+			| Event::Restored
+			| Event::Minimized
+			| Event::Maximized
+			| Event::FocusGained
+			| Event::FocusLost
+			| Event::FileDropped { .. } => Self::Window,
+			Event::KeyPressed { .. } | Event::KeyReleased { .. } => Self::Keyboard,
+			Event::TextInput { .. } => Self::Text,
+			Event::MouseButtonPressed { .. } | Event::MouseButtonReleased { .. } => {
+				Self::MouseButton
+			}
+			Event::MouseMoved { .. } => Self::MouseMotion,
+			Event::MouseWheelMoved { .. } => Self::MouseWheel,
+			Event::GamepadAdded { .. }
+			| Event::GamepadRemoved { .. }
+			| Event::GamepadButtonPressed { .. }
+			| Event::GamepadButtonReleased { .. }
+			| Event::GamepadAxisMoved { .. }
+			| Event::GamepadStickMoved { .. } => Self::Gamepad,
+			_ => Self::Other,
+		}
+	}
+
+	fn label(self) -> &'static str {
+		match self {
+			Self::Window => "Window",
+			Self::Keyboard => "Keyboard",
+			Self::Text => "Text",
+			Self::MouseButton => "Mouse buttons",
+			Self::MouseMotion => "Mouse motion",
+			Self::MouseWheel => "Mouse wheel",
+			Self::Gamepad => "Gamepad",
+			Self::Other => "Other",
+		}
+	}
+}
+
+fn delivery_label(delivery: EventDelivery) -> (&'static str, egui::Color32) {
+	match delivery {
+		EventDelivery::Game => ("game", egui::Color32::LIGHT_GREEN),
+		EventDelivery::Plugin => ("plugin", egui::Color32::LIGHT_BLUE),
+		EventDelivery::Gui => ("egui", egui::Color32::YELLOW),
+		EventDelivery::OutsideViewport => ("outside viewport", egui::Color32::GRAY),
+		EventDelivery::Toggle => ("toggle", egui::Color32::GRAY),
+		EventDelivery::Paused => ("paused", egui::Color32::GRAY),
+	}
+}
+
+/// A [`Plugin`] that shows a window listing every event the
+/// [`StateWrapper`](crate::StateWrapper) receives.
+///
+/// Each event is shown with the time it was received and whether it was
+/// sent to the game, consumed by a plugin, used by egui, or dropped
+/// because it happened outside of the [`Viewport`](crate::Viewport).
+/// Event types can be filtered using the checkboxes at the top of the
+/// window.
+pub struct EventLogPanel {
+	records: VecDeque<EventRecord>,
+	max_records: usize,
+	start_time: Instant,
+	shown_kinds: Vec<EventKind>,
+	paused: bool,
+	open: bool,
+}
+
+impl EventLogPanel {
+	/// Creates a new [`EventLogPanel`].
+	///
+	/// Mouse motion events are hidden by default.
+	pub fn new() -> Self {
+		Self {
+			records: VecDeque::new(),
+			max_records: DEFAULT_MAX_RECORDS,
+			start_time: Instant::now(),
+			shown_kinds: EventKind::ALL
+				.iter()
+				.copied()
+				.filter(|kind| *kind != EventKind::MouseMotion)
+				.collect(),
+			paused: false,
+			open: true,
+		}
+	}
+
+	/// Sets how many events to keep and returns the panel.
+	///
+	/// Defaults to 500.
+	pub fn max_records(mut self, max_records: usize) -> Self {
+		self.max_records = max_records.max(1);
+		self
+	}
+
+	/// Sets whether the window is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Returns the recorded events, from oldest to newest.
+	pub fn records(&self) -> impl Iterator<Item = &EventRecord> {
+		self.records.iter()
+	}
+
+	/// Removes all recorded events.
+	pub fn clear(&mut self) {
+		self.records.clear();
+	}
+
+	fn filters_ui(&mut self, ui: &mut egui::Ui) {
+		ui.horizontal_wrapped(|ui| {
+			for kind in EventKind::ALL {
+				let mut shown = self.shown_kinds.contains(&kind);
+				if ui.checkbox(&mut shown, kind.label()).changed() {
+					if shown {
+						self.shown_kinds.push(kind);
+					} else {
+						self.shown_kinds.retain(|shown_kind| *shown_kind != kind);
+					}
+				}
+			}
+		});
+	}
+
+	fn records_ui(&self, ui: &mut egui::Ui) {
+		egui::ScrollArea::vertical()
+			.stick_to_bottom()
+			.show(ui, |ui| {
+				egui::Grid::new("egui_tetra_event_log")
+					.striped(true)
+					.show(ui, |ui| {
+						for record in self.records.iter().filter(|record| {
+							self.shown_kinds.contains(&EventKind::of(&record.event))
+						}) {
+							let time = record.time.saturating_duration_since(self.start_time);
+							ui.monospace(format!("{:>9.3}", time.as_secs_f64()));
+							let (label, color) = delivery_label(record.delivery);
+							ui.colored_label(color, label);
+							ui.monospace(format!("{:?}", record.event));
+							ui.end_row();
+						}
+					});
+			});
+	}
+}
+
+impl Default for EventLogPanel {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E: From<Error>> Plugin<E> for EventLogPanel {
+	fn ui(&mut self, _ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let mut open = self.open;
+		egui::Window::new("Event log")
+			.open(&mut open)
+			.show(egui_ctx, |ui| {
+				ui.horizontal(|ui| {
+					if ui
+						.button(if self.paused { "Resume" } else { "Pause" })
+						.clicked()
+					{
+						self.paused = !self.paused;
+					}
+					if ui.button("Clear").clicked() {
+						self.clear();
+					}
+				});
+				self.filters_ui(ui);
+				ui.separator();
+				self.records_ui(ui);
+			});
+		self.open = open;
+		Ok(())
+	}
+
+	fn inspect_event(
+		&mut self,
+		_ctx: &mut tetra::Context,
+		_egui_ctx: &egui::CtxRef,
+		record: &EventRecord,
+	) -> Result<(), E> {
+		if self.paused {
+			return Ok(());
+		}
+		self.records.push_back(record.clone());
+		while self.records.len() > self.max_records {
+			self.records.pop_front();
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,210 @@
+use tetra::input::{self, GamepadAxis, GamepadButton, GamepadStick, KeyModifier, MouseButton};
+
+use crate::{Error, Plugin};
+
+/// The number of gamepad slots to check for connected gamepads.
+const MAX_GAMEPADS: usize = 8;
+
+const MOUSE_BUTTONS: [MouseButton; 5] = [
+	MouseButton::Left,
+	MouseButton::Middle,
+	MouseButton::Right,
+	MouseButton::X1,
+	MouseButton::X2,
+];
+
+const GAMEPAD_BUTTONS: [GamepadButton; 17] = [
+	GamepadButton::A,
+	GamepadButton::B,
+	GamepadButton::X,
+	GamepadButton::Y,
+	GamepadButton::Up,
+	GamepadButton::Down,
+	GamepadButton::Left,
+	GamepadButton::Right,
+	GamepadButton::LeftShoulder,
+	GamepadButton::LeftTrigger,
+	GamepadButton::LeftStick,
+	GamepadButton::RightShoulder,
+	GamepadButton::RightTrigger,
+	GamepadButton::RightStick,
+	GamepadButton::Start,
+	GamepadButton::Back,
+	GamepadButton::Guide,
+];
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+	GamepadAxis::LeftStickX,
+	GamepadAxis::LeftStickY,
+	GamepadAxis::LeftTrigger,
+	GamepadAxis::RightStickX,
+	GamepadAxis::RightStickY,
+	GamepadAxis::RightTrigger,
+];
+
+/// Shows a label that is highlighted while `down` is `true`.
+fn indicator(ui: &mut egui::Ui, text: impl ToString, down: bool) {
+	let text = egui::RichText::new(text.to_string()).monospace();
+	if down {
+		ui.label(
+			text.strong()
+				.color(ui.visuals().strong_text_color())
+				.background_color(ui.visuals().selection.bg_fill),
+		);
+	} else {
+		ui.label(text.weak());
+	}
+}
+
+/// Draws a stick's position inside a circle.
+fn stick_ui(ui: &mut egui::Ui, position: tetra::math::Vec2<f32>) {
+	let (rect, _) = ui.allocate_exact_size(egui::vec2(48.0, 48.0), egui::Sense::hover());
+	let painter = ui.painter_at(rect);
+	let radius = rect.width() / 2.0 - 2.0;
+	let stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+	painter.circle_stroke(rect.center(), radius, stroke);
+	painter.circle_filled(
+		rect.center() + egui::vec2(position.x, position.y) * radius,
+		3.0,
+		ui.visuals().selection.stroke.color,
+	);
+}
+
+/// A [`Plugin`] that shows a window with the current state of
+/// Tetra's keyboard, mouse, and gamepad input.
+///
+/// Along with the keys Tetra considers held down, the window shows the
+/// modifiers egui thinks are held, which makes it easy to spot modifiers
+/// that are stuck in one but not the other.
+///
+/// The contents of the window can also be shown inside another
+/// [`Ui`](egui::Ui) with [`InputPanel::show_inside`].
+pub struct InputPanel {
+	open: bool,
+}
+
+impl InputPanel {
+	/// Creates a new [`InputPanel`].
+	pub fn new() -> Self {
+		Self { open: true }
+	}
+
+	/// Sets whether the window is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Shows the current input state inside a [`Ui`](egui::Ui).
+	pub fn show_inside(&mut self, ctx: &tetra::Context, ui: &mut egui::Ui) {
+		ui.heading("Keyboard");
+		ui.horizontal_wrapped(|ui| {
+			ui.label("Keys down:");
+			let mut any_down = false;
+			for key in input::get_keys_down(ctx) {
+				indicator(ui, format!("{:?}", key), true);
+				any_down = true;
+			}
+			if !any_down {
+				ui.label(egui::RichText::new("none").weak());
+			}
+		});
+		let egui_modifiers = ui.input().modifiers;
+		egui::Grid::new("egui_tetra_input_modifiers").show(ui, |ui| {
+			ui.label("");
+			ui.label("Ctrl");
+			ui.label("Alt");
+			ui.label("Shift");
+			ui.end_row();
+			ui.label("Tetra");
+			for modifier in [KeyModifier::Ctrl, KeyModifier::Alt, KeyModifier::Shift] {
+				indicator(ui, "down", input::is_key_modifier_down(ctx, modifier));
+			}
+			ui.end_row();
+			ui.label("egui");
+			indicator(ui, "down", egui_modifiers.ctrl);
+			indicator(ui, "down", egui_modifiers.alt);
+			indicator(ui, "down", egui_modifiers.shift);
+			ui.end_row();
+		});
+
+		ui.separator();
+		ui.heading("Mouse");
+		let position = input::get_mouse_position(ctx);
+		ui.label(format!("Position: ({:.1}, {:.1})", position.x, position.y));
+		let wheel = input::get_mouse_wheel_movement(ctx);
+		ui.label(format!("Wheel: ({}, {})", wheel.x, wheel.y));
+		ui.horizontal_wrapped(|ui| {
+			ui.label("Buttons:");
+			for button in MOUSE_BUTTONS {
+				indicator(
+					ui,
+					format!("{:?}", button),
+					input::is_mouse_button_down(ctx, button),
+				);
+			}
+		});
+
+		ui.separator();
+		ui.heading("Gamepads");
+		let mut any_connected = false;
+		for id in (0..MAX_GAMEPADS).filter(|id| input::is_gamepad_connected(ctx, *id)) {
+			any_connected = true;
+			let name = input::get_gamepad_name(ctx, id).unwrap_or_default();
+			egui::CollapsingHeader::new(format!("{}: {}", id, name))
+				.id_source(("egui_tetra_input_gamepad", id))
+				.default_open(true)
+				.show(ui, |ui| {
+					ui.horizontal_wrapped(|ui| {
+						for button in GAMEPAD_BUTTONS {
+							indicator(
+								ui,
+								format!("{:?}", button),
+								input::is_gamepad_button_down(ctx, id, button),
+							);
+						}
+					});
+					egui::Grid::new(("egui_tetra_input_gamepad_axes", id)).show(ui, |ui| {
+						for axis in GAMEPAD_AXES {
+							ui.label(format!("{:?}", axis));
+							ui.monospace(format!(
+								"{:+.3}",
+								input::get_gamepad_axis_position(ctx, id, axis)
+							));
+							ui.end_row();
+						}
+					});
+					ui.horizontal(|ui| {
+						stick_ui(
+							ui,
+							input::get_gamepad_stick_position(ctx, id, GamepadStick::LeftStick),
+						);
+						stick_ui(
+							ui,
+							input::get_gamepad_stick_position(ctx, id, GamepadStick::RightStick),
+						);
+					});
+				});
+		}
+		if !any_connected {
+			ui.label(egui::RichText::new("No gamepads connected").weak());
+		}
+	}
+}
+
+impl Default for InputPanel {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E: From<Error>> Plugin<E> for InputPanel {
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let mut open = self.open;
+		egui::Window::new("Input")
+			.open(&mut open)
+			.show(egui_ctx, |ui| self.show_inside(ctx, ui));
+		self.open = open;
+		Ok(())
+	}
+}
@@ -0,0 +1,200 @@
+use std::{
+	collections::{BTreeSet, HashSet},
+	time::Instant,
+};
+
+use log::Level;
+
+use crate::{Error, LogBuffer, LogRecord, Plugin};
+
+const LEVELS: [Level; 5] = [
+	Level::Error,
+	Level::Warn,
+	Level::Info,
+	Level::Debug,
+	Level::Trace,
+];
+
+fn level_color(level: Level) -> egui::Color32 {
+	match level {
+		Level::Error => egui::Color32::LIGHT_RED,
+		Level::Warn => egui::Color32::YELLOW,
+		Level::Info => egui::Color32::LIGHT_GREEN,
+		Level::Debug => egui::Color32::LIGHT_BLUE,
+		Level::Trace => egui::Color32::GRAY,
+	}
+}
+
+/// A [`Plugin`] that shows a window listing the messages captured by
+/// a [`Logger`](crate::Logger).
+///
+/// Messages can be filtered by level and target using the checkboxes at
+/// the top of the window, or searched by text. The "Copy" button copies
+/// the messages that are currently shown to the clipboard. Only the first
+/// line of a message is listed, and the whole message is shown when it's
+/// hovered.
+///
+/// This requires the `log` feature.
+pub struct LogPanel {
+	buffer: LogBuffer,
+	start_time: Instant,
+	shown_levels: HashSet<Level>,
+	hidden_targets: HashSet<String>,
+	search: String,
+	auto_scroll: bool,
+	open: bool,
+}
+
+impl LogPanel {
+	/// Creates a new [`LogPanel`] showing the messages in a
+	/// [`LogBuffer`].
+	pub fn new(buffer: LogBuffer) -> Self {
+		Self {
+			buffer,
+			start_time: Instant::now(),
+			shown_levels: LEVELS.iter().copied().collect(),
+			hidden_targets: HashSet::new(),
+			search: String::new(),
+			auto_scroll: true,
+			open: true,
+		}
+	}
+
+	/// Sets whether the window is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Returns the buffer the panel shows messages from.
+	pub fn buffer(&self) -> &LogBuffer {
+		&self.buffer
+	}
+
+	fn is_shown(&self, record: &LogRecord, search: &str) -> bool {
+		self.shown_levels.contains(&record.level)
+			&& !self.hidden_targets.contains(&record.target)
+			&& (search.is_empty()
+				|| record.message.to_lowercase().contains(search)
+				|| record.target.to_lowercase().contains(search))
+	}
+
+	fn format_record(&self, record: &LogRecord) -> String {
+		let time = record.time.saturating_duration_since(self.start_time);
+		format!(
+			"{:>9.3} {:<5} {} {}",
+			time.as_secs_f64(),
+			record.level,
+			record.target,
+			record.message
+		)
+	}
+
+	fn filters_ui(&mut self, ui: &mut egui::Ui, targets: &BTreeSet<String>) {
+		ui.horizontal_wrapped(|ui| {
+			for level in LEVELS {
+				let mut shown = self.shown_levels.contains(&level);
+				if ui.checkbox(&mut shown, level.as_str()).changed() {
+					if shown {
+						self.shown_levels.insert(level);
+					} else {
+						self.shown_levels.remove(&level);
+					}
+				}
+			}
+		});
+		ui.collapsing("Targets", |ui| {
+			if targets.is_empty() {
+				ui.label(egui::RichText::new("No messages logged").weak());
+			}
+			for target in targets {
+				let mut shown = !self.hidden_targets.contains(target);
+				if ui.checkbox(&mut shown, target).changed() {
+					if shown {
+						self.hidden_targets.remove(target);
+					} else {
+						self.hidden_targets.insert(target.clone());
+					}
+				}
+			}
+		});
+	}
+
+	fn records_ui(&self, ui: &mut egui::Ui, records: &[&LogRecord]) {
+		let mut scroll_area = egui::ScrollArea::vertical();
+		if self.auto_scroll {
+			scroll_area = scroll_area.stick_to_bottom();
+		}
+		let row_height = {
+			let fonts = ui.fonts();
+			fonts
+				.row_height(egui::TextStyle::Body)
+				.max(fonts.row_height(egui::TextStyle::Monospace))
+		};
+		// only the rows that are scrolled into view are laid out, so
+		// long logs don't slow down the UI
+		scroll_area.show_rows(ui, row_height, records.len(), |ui, range| {
+			egui::Grid::new("egui_tetra_log")
+				.striped(true)
+				.show(ui, |ui| {
+					for record in &records[range] {
+						let time = record.time.saturating_duration_since(self.start_time);
+						ui.monospace(format!("{:>9.3}", time.as_secs_f64()));
+						ui.colored_label(level_color(record.level), record.level.as_str());
+						ui.label(egui::RichText::new(&record.target).weak());
+						// every row has to be the same height, so only the
+						// first line of a message is shown, without wrapping
+						let mut lines = record.message.lines();
+						let first_line = lines.next().unwrap_or_default();
+						let response = ui.add(
+							egui::Label::new(egui::RichText::new(first_line).monospace())
+								.wrap(false),
+						);
+						if lines.next().is_some() {
+							response.on_hover_text(&record.message);
+						}
+						ui.end_row();
+					}
+				});
+		});
+	}
+}
+
+impl<E: From<Error>> Plugin<E> for LogPanel {
+	fn ui(&mut self, _ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let mut open = self.open;
+		egui::Window::new("Log")
+			.open(&mut open)
+			.show(egui_ctx, |ui| {
+				let all_records = self.buffer.records();
+				let targets = all_records
+					.iter()
+					.map(|record| record.target.clone())
+					.collect::<BTreeSet<_>>();
+				let search = self.search.to_lowercase();
+				let records = all_records
+					.iter()
+					.filter(|record| self.is_shown(record, &search))
+					.collect::<Vec<_>>();
+				ui.horizontal(|ui| {
+					ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search"));
+					ui.checkbox(&mut self.auto_scroll, "Auto-scroll");
+					if ui.button("Copy").clicked() {
+						ui.output().copied_text = records
+							.iter()
+							.map(|record| self.format_record(record))
+							.collect::<Vec<_>>()
+							.join("\n");
+					}
+					if ui.button("Clear").clicked() {
+						self.buffer.clear();
+					}
+				});
+				self.filters_ui(ui, &targets);
+				ui.separator();
+				self.records_ui(ui, &records);
+			});
+		self.open = open;
+		Ok(())
+	}
+}
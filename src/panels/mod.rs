@@ -0,0 +1,21 @@
+#[cfg(feature = "audio")]
+mod audio;
+mod console;
+mod event_log;
+mod input;
+#[cfg(feature = "log")]
+mod log;
+mod performance;
+mod time_control;
+mod window;
+
+#[cfg(feature = "log")]
+pub use self::log::LogPanel;
+#[cfg(feature = "audio")]
+pub use audio::{RegisteredSound, SoundRegistry};
+pub use console::ConsolePanel;
+pub use event_log::EventLogPanel;
+pub use input::InputPanel;
+pub use performance::PerformancePanel;
+pub use time_control::TimeControlPanel;
+pub use window::WindowPanel;
@@ -0,0 +1,241 @@
+use std::{collections::VecDeque, time::Instant};
+
+use egui::plot::{Line, Plot, Value, Values};
+
+use crate::{Error, FrameStats, Plugin, TimeControls};
+
+const DEFAULT_MAX_SAMPLES: usize = 240;
+const PERCENTILES: [f64; 3] = [50.0, 95.0, 99.0];
+
+/// Measurements taken for a single drawn frame.
+struct Sample {
+	/// The time since the previous frame was drawn, in milliseconds.
+	frame_time: f64,
+	/// The number of gameplay ticks run since the previous frame.
+	ticks: u64,
+	/// The GUI statistics for the frame.
+	stats: FrameStats,
+}
+
+/// The minimum, average, maximum, and [`PERCENTILES`] of a set of values.
+struct Summary {
+	min: f64,
+	avg: f64,
+	max: f64,
+	percentiles: [f64; PERCENTILES.len()],
+}
+
+impl Summary {
+	fn new(values: impl Iterator<Item = f64>) -> Option<Self> {
+		let mut values: Vec<f64> = values.collect();
+		if values.is_empty() {
+			return None;
+		}
+		values.sort_by(|a, b| a.total_cmp(b));
+		let percentile = |percent: f64| {
+			let index = (percent / 100.0 * (values.len() - 1) as f64).round() as usize;
+			values[index]
+		};
+		Some(Self {
+			min: values[0],
+			avg: values.iter().sum::<f64>() / values.len() as f64,
+			max: values[values.len() - 1],
+			percentiles: PERCENTILES.map(percentile),
+		})
+	}
+}
+
+/// A [`Plugin`] that shows a window with performance statistics for
+/// the game and the GUI.
+///
+/// The window shows the frame rate, a graph of recent frame times, and
+/// the minimum, average, maximum, and percentiles of:
+/// - The frame time
+/// - The number of gameplay ticks per frame
+/// - The time spent constructing, tessellating, and drawing the GUI
+/// - The number of meshes and vertices the GUI was drawn with
+/// - The number of buffers and textures uploaded to the GPU for the GUI
+///
+/// GUI statistics come from the [`FrameStats`] of the context the plugin
+/// is added to, and lag one frame behind the frame times. Gameplay ticks
+/// are the game updates actually run by [`TimeControls`], so they're zero
+/// while gameplay is paused. They're only counted when the plugin is added
+/// to the main context.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # struct MainState;
+/// # impl egui_tetra::State<Box<dyn Error>> for MainState {}
+/// use egui_tetra::{PerformancePanel, StateWrapper};
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// 	tetra::ContextBuilder::new("example", 800, 600)
+/// 		.build()?
+/// 		.run(|_| Ok(StateWrapper::new(MainState).with_plugin(PerformancePanel::new())))
+/// }
+/// ```
+pub struct PerformancePanel {
+	samples: VecDeque<Sample>,
+	max_samples: usize,
+	paused: bool,
+	open: bool,
+	/// The total number of gameplay ticks as of the previous frame.
+	last_ticks: Option<u64>,
+	last_draw_time: Option<Instant>,
+}
+
+impl PerformancePanel {
+	/// Creates a new [`PerformancePanel`].
+	pub fn new() -> Self {
+		Self {
+			samples: VecDeque::new(),
+			max_samples: DEFAULT_MAX_SAMPLES,
+			paused: false,
+			open: true,
+			last_ticks: None,
+			last_draw_time: None,
+		}
+	}
+
+	/// Sets how many frames of history to keep and returns the panel.
+	///
+	/// Defaults to 240.
+	pub fn max_samples(mut self, max_samples: usize) -> Self {
+		self.max_samples = max_samples.max(1);
+		self
+	}
+
+	/// Sets whether the window is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Returns `true` if the panel has stopped recording new frames.
+	pub fn is_paused(&self) -> bool {
+		self.paused
+	}
+
+	/// Stops or resumes recording new frames.
+	pub fn set_paused(&mut self, paused: bool) {
+		self.paused = paused;
+	}
+
+	/// Removes all recorded frames.
+	pub fn clear(&mut self) {
+		self.samples.clear();
+	}
+
+	fn summary_row(&self, ui: &mut egui::Ui, label: &str, value: impl Fn(&Sample) -> f64) {
+		ui.label(label);
+		match Summary::new(self.samples.iter().map(value)) {
+			Some(summary) => {
+				ui.label(format!("{:.2}", summary.min));
+				ui.label(format!("{:.2}", summary.avg));
+				ui.label(format!("{:.2}", summary.max));
+				for percentile in summary.percentiles {
+					ui.label(format!("{:.2}", percentile));
+				}
+			}
+			None => {
+				for _ in 0..3 + PERCENTILES.len() {
+					ui.label("-");
+				}
+			}
+		}
+		ui.end_row();
+	}
+}
+
+impl Default for PerformancePanel {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E: From<Error>> Plugin<E> for PerformancePanel {
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let mut open = self.open;
+		egui::Window::new("Performance")
+			.open(&mut open)
+			.show(egui_ctx, |ui| {
+				ui.horizontal(|ui| {
+					ui.label(format!("FPS: {:.1}", tetra::time::get_fps(ctx)));
+					if ui
+						.button(if self.paused { "Resume" } else { "Pause" })
+						.clicked()
+					{
+						self.paused = !self.paused;
+					}
+					if ui.button("Clear").clicked() {
+						self.clear();
+					}
+				});
+				Plot::new("egui_tetra_performance_frame_times")
+					.height(100.0)
+					.include_y(0.0)
+					.allow_drag(false)
+					.allow_zoom(false)
+					.show(ui, |plot_ui| {
+						plot_ui.line(Line::new(Values::from_values_iter(
+							self.samples
+								.iter()
+								.enumerate()
+								.map(|(i, sample)| Value::new(i as f64, sample.frame_time)),
+						)))
+					});
+				egui::Grid::new("egui_tetra_performance_stats")
+					.striped(true)
+					.show(ui, |ui| {
+						ui.label("");
+						ui.strong("Min");
+						ui.strong("Avg");
+						ui.strong("Max");
+						for percentile in PERCENTILES {
+							ui.strong(format!("P{}", percentile));
+						}
+						ui.end_row();
+						self.summary_row(ui, "Frame time (ms)", |sample| sample.frame_time);
+						self.summary_row(ui, "Game ticks", |sample| sample.ticks as f64);
+						self.summary_row(ui, "UI time (ms)", |sample| {
+							sample.stats.ui_time.as_secs_f64() * 1000.0
+						});
+						self.summary_row(ui, "Tessellation (ms)", |sample| {
+							sample.stats.tessellation_time.as_secs_f64() * 1000.0
+						});
+						self.summary_row(ui, "GUI draw (ms)", |sample| {
+							sample.stats.draw_time.as_secs_f64() * 1000.0
+						});
+						self.summary_row(ui, "Meshes", |sample| sample.stats.mesh_count as f64);
+						self.summary_row(ui, "Vertices", |sample| sample.stats.vertex_count as f64);
+						self.summary_row(ui, "Buffer uploads", |sample| {
+							sample.stats.buffer_uploads as f64
+						});
+					});
+			});
+		self.open = open;
+		Ok(())
+	}
+
+	fn draw(&mut self, _ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let now = Instant::now();
+		let total_ticks = TimeControls::get(egui_ctx).ticks();
+		let ticks =
+			total_ticks.saturating_sub(self.last_ticks.replace(total_ticks).unwrap_or(total_ticks));
+		let last_draw_time = self.last_draw_time.replace(now);
+		if self.paused {
+			return Ok(());
+		}
+		if let Some(last_draw_time) = last_draw_time {
+			self.samples.push_back(Sample {
+				frame_time: (now - last_draw_time).as_secs_f64() * 1000.0,
+				ticks,
+				stats: FrameStats::latest(egui_ctx).unwrap_or_default(),
+			});
+			while self.samples.len() > self.max_samples {
+				self.samples.pop_front();
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,162 @@
+use tetra::{input::Key, Event};
+
+use crate::{Error, Plugin, TimeControls, MAX_TIME_SCALE};
+
+/// A [`Plugin`] that shows a window for pausing, stepping, and changing
+/// the speed of gameplay with [`TimeControls`].
+///
+/// Keys can also be set for pausing and stepping. Presses of those keys
+/// are consumed by the plugin, so they aren't sent to the game.
+///
+/// The time controls are stored in the main context, so this plugin
+/// should be added to the main context.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # struct MainState;
+/// # impl egui_tetra::State<Box<dyn Error>> for MainState {}
+/// use egui_tetra::{StateWrapper, TimeControlPanel};
+/// use tetra::input::Key;
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// 	tetra::ContextBuilder::new("example", 800, 600)
+/// 		.build()?
+/// 		.run(|_| {
+/// 			Ok(StateWrapper::new(MainState).with_plugin(
+/// 				TimeControlPanel::new()
+/// 					.pause_key(Key::F5)
+/// 					.step_key(Key::F6),
+/// 			))
+/// 		})
+/// }
+/// ```
+pub struct TimeControlPanel {
+	open: bool,
+	step_count: u32,
+	pause_key: Option<Key>,
+	step_key: Option<Key>,
+}
+
+impl TimeControlPanel {
+	/// Creates a new [`TimeControlPanel`].
+	pub fn new() -> Self {
+		Self {
+			open: true,
+			step_count: 10,
+			pause_key: None,
+			step_key: None,
+		}
+	}
+
+	/// Sets whether the window is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Sets a key that pauses and resumes gameplay and returns the panel.
+	pub fn pause_key(mut self, key: Key) -> Self {
+		self.pause_key = Some(key);
+		self
+	}
+
+	/// Sets a key that steps gameplay forward by one update and returns
+	/// the panel.
+	pub fn step_key(mut self, key: Key) -> Self {
+		self.step_key = Some(key);
+		self
+	}
+
+	/// Shows the time controls inside an existing [`Ui`](egui::Ui).
+	pub fn show_inside(&mut self, egui_ctx: &egui::CtxRef, ui: &mut egui::Ui) {
+		let mut controls = TimeControls::get(egui_ctx);
+		ui.horizontal(|ui| {
+			if ui
+				.button(if controls.is_paused() {
+					"Resume"
+				} else {
+					"Pause"
+				})
+				.clicked()
+			{
+				controls.set_paused(!controls.is_paused());
+			}
+			if ui.button("Step").clicked() {
+				controls.step(1);
+			}
+		});
+		ui.horizontal(|ui| {
+			if ui.button(format!("Step {}", self.step_count)).clicked() {
+				controls.step(self.step_count);
+			}
+			ui.add(
+				egui::DragValue::new(&mut self.step_count)
+					.clamp_range(1..=10000)
+					.suffix(" ticks"),
+			);
+		});
+		ui.horizontal(|ui| {
+			let mut time_scale = controls.time_scale();
+			if ui
+				.add(
+					egui::Slider::new(&mut time_scale, 0.0..=MAX_TIME_SCALE)
+						.logarithmic(true)
+						.text("Time scale"),
+				)
+				.changed()
+			{
+				controls.set_time_scale(time_scale);
+			}
+			if ui.button("Reset").clicked() {
+				controls.set_time_scale(1.0);
+			}
+		});
+		ui.label(match (controls.is_paused(), controls.pending_steps()) {
+			(false, _) => "Running".to_string(),
+			(true, 0) => "Paused".to_string(),
+			(true, steps) => format!("Stepping ({} left)", steps),
+		});
+		ui.label(format!("Gameplay ticks: {}", controls.ticks()));
+		if controls != TimeControls::get(egui_ctx) {
+			TimeControls::edit(egui_ctx, |current| *current = controls);
+		}
+	}
+}
+
+impl Default for TimeControlPanel {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E: From<Error>> Plugin<E> for TimeControlPanel {
+	fn ui(&mut self, _ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let mut open = self.open;
+		egui::Window::new("Time controls")
+			.open(&mut open)
+			.show(egui_ctx, |ui| self.show_inside(egui_ctx, ui));
+		self.open = open;
+		Ok(())
+	}
+
+	fn event(
+		&mut self,
+		_ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		event: &Event,
+	) -> Result<bool, E> {
+		if let Event::KeyPressed { key } = event {
+			if self.pause_key == Some(*key) {
+				TimeControls::edit(egui_ctx, |controls| {
+					controls.set_paused(!controls.is_paused())
+				});
+				return Ok(true);
+			}
+			if self.step_key == Some(*key) {
+				TimeControls::edit(egui_ctx, |controls| controls.step(1));
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
+}
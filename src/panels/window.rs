@@ -0,0 +1,230 @@
+use tetra::{
+	time::{self, Timestep},
+	window::{self, WindowPosition},
+};
+
+use crate::{Error, Plugin};
+
+const DEFAULT_SIZE_PRESETS: [(i32, i32); 4] = [(640, 360), (1280, 720), (1600, 900), (1920, 1080)];
+const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// A change to the window or timestep settings requested from
+/// the [`WindowPanel`].
+enum Change {
+	Fullscreen(bool),
+	Vsync(bool),
+	Size(i32, i32),
+	Title(String),
+	MouseVisible(bool),
+	RelativeMouseMode(bool),
+	Monitor(i32),
+	Timestep(Timestep),
+}
+
+impl Change {
+	fn apply(self, ctx: &mut tetra::Context) -> tetra::Result {
+		match self {
+			Self::Fullscreen(fullscreen) => window::set_fullscreen(ctx, fullscreen)?,
+			Self::Vsync(vsync) => window::set_vsync(ctx, vsync)?,
+			Self::Size(width, height) => window::set_size(ctx, width, height)?,
+			Self::Title(title) => window::set_title(ctx, title),
+			Self::MouseVisible(visible) => window::set_mouse_visible(ctx, visible)?,
+			Self::RelativeMouseMode(relative) => window::set_relative_mouse_mode(ctx, relative),
+			Self::Monitor(monitor) => window::set_position(
+				ctx,
+				WindowPosition::Centered(monitor),
+				WindowPosition::Centered(monitor),
+			),
+			Self::Timestep(timestep) => time::set_timestep(ctx, timestep),
+		}
+		Ok(())
+	}
+}
+
+/// A [`Plugin`] that shows a window for changing the game window's
+/// settings and the game's timestep.
+///
+/// The window has controls for fullscreen, vsync, the window size, title,
+/// mouse visibility, relative mouse mode, which monitor the window is on,
+/// and switching between a fixed and variable timestep. Changes are applied
+/// through [`tetra::window`] and [`tetra::time`] as soon as they're made.
+pub struct WindowPanel {
+	open: bool,
+	size_presets: Vec<(i32, i32)>,
+	/// The title shown in the title field, which is kept in sync with
+	/// the window's title while it isn't being edited.
+	title: String,
+	tick_rate: f64,
+}
+
+impl WindowPanel {
+	/// Creates a new [`WindowPanel`].
+	pub fn new() -> Self {
+		Self {
+			open: true,
+			size_presets: DEFAULT_SIZE_PRESETS.to_vec(),
+			title: String::new(),
+			tick_rate: DEFAULT_TICK_RATE,
+		}
+	}
+
+	/// Sets whether the window is initially open and returns the panel.
+	pub fn open(mut self, open: bool) -> Self {
+		self.open = open;
+		self
+	}
+
+	/// Sets the window sizes that can be picked with a single click
+	/// and returns the panel.
+	///
+	/// Defaults to 640x360, 1280x720, 1600x900, and 1920x1080.
+	pub fn size_presets(mut self, size_presets: impl IntoIterator<Item = (i32, i32)>) -> Self {
+		self.size_presets = size_presets.into_iter().collect();
+		self
+	}
+
+	/// Shows the settings inside a [`Ui`](egui::Ui), and applies any
+	/// changes made to them.
+	pub fn show_inside(
+		&mut self,
+		ctx: &mut tetra::Context,
+		ui: &mut egui::Ui,
+	) -> Result<(), Error> {
+		for change in self.settings_ui(ctx, ui) {
+			change.apply(ctx)?;
+		}
+		Ok(())
+	}
+
+	fn settings_ui(&mut self, ctx: &tetra::Context, ui: &mut egui::Ui) -> Vec<Change> {
+		let mut changes = vec![];
+		ui.heading("Window");
+		let mut fullscreen = window::is_fullscreen(ctx);
+		if ui.checkbox(&mut fullscreen, "Fullscreen").changed() {
+			changes.push(Change::Fullscreen(fullscreen));
+		}
+		let mut vsync = window::is_vsync_enabled(ctx);
+		if ui.checkbox(&mut vsync, "Vsync").changed() {
+			changes.push(Change::Vsync(vsync));
+		}
+		let (width, height) = window::get_size(ctx);
+		ui.horizontal_wrapped(|ui| {
+			ui.label(format!("Size: {}x{}", width, height));
+			for &(preset_width, preset_height) in &self.size_presets {
+				if ui
+					.selectable_label(
+						(preset_width, preset_height) == (width, height),
+						format!("{}x{}", preset_width, preset_height),
+					)
+					.clicked()
+				{
+					changes.push(Change::Size(preset_width, preset_height));
+				}
+			}
+		});
+		ui.horizontal(|ui| {
+			ui.label("Title:");
+			let id = ui.make_persistent_id("egui_tetra_window_title");
+			let current_title = window::get_title(ctx);
+			if !ui.memory().has_focus(id) {
+				self.title.clear();
+				self.title.push_str(current_title);
+			}
+			let response = ui.add(egui::TextEdit::singleline(&mut self.title).id(id));
+			let apply = ui.button("Apply").clicked();
+			if (response.lost_focus() || apply) && self.title != current_title {
+				changes.push(Change::Title(self.title.clone()));
+			}
+		});
+		let mut mouse_visible = window::is_mouse_visible(ctx);
+		if ui.checkbox(&mut mouse_visible, "Show mouse").changed() {
+			changes.push(Change::MouseVisible(mouse_visible));
+		}
+		let mut relative_mouse_mode = window::is_relative_mouse_mode(ctx);
+		if ui
+			.checkbox(&mut relative_mouse_mode, "Relative mouse mode")
+			.changed()
+		{
+			changes.push(Change::RelativeMouseMode(relative_mouse_mode));
+		}
+		let monitor_count = window::get_monitor_count(ctx).unwrap_or(0);
+		let current_monitor = window::get_current_monitor(ctx).unwrap_or(0);
+		let monitor_label = |monitor: i32| {
+			let name = window::get_monitor_name(ctx, monitor).unwrap_or_default();
+			match window::get_monitor_size(ctx, monitor) {
+				Ok((width, height)) => format!("{}: {} ({}x{})", monitor, name, width, height),
+				Err(_) => format!("{}: {}", monitor, name),
+			}
+		};
+		egui::ComboBox::from_label("Monitor")
+			.selected_text(monitor_label(current_monitor))
+			.show_ui(ui, |ui| {
+				for monitor in 0..monitor_count {
+					if ui
+						.selectable_label(monitor == current_monitor, monitor_label(monitor))
+						.clicked() && monitor != current_monitor
+					{
+						changes.push(Change::Monitor(monitor));
+					}
+				}
+			});
+
+		ui.separator();
+		ui.heading("Timestep");
+		let timestep = time::get_timestep(ctx);
+		if let Timestep::Fixed(tick_rate) = timestep {
+			self.tick_rate = tick_rate;
+		}
+		ui.horizontal(|ui| {
+			if ui
+				.radio(matches!(timestep, Timestep::Fixed(_)), "Fixed")
+				.clicked()
+			{
+				changes.push(Change::Timestep(Timestep::Fixed(self.tick_rate)));
+			}
+			if ui
+				.radio(matches!(timestep, Timestep::Variable), "Variable")
+				.clicked()
+			{
+				changes.push(Change::Timestep(Timestep::Variable));
+			}
+		});
+		ui.add_enabled_ui(matches!(timestep, Timestep::Fixed(_)), |ui| {
+			ui.horizontal(|ui| {
+				ui.label("Tick rate:");
+				if ui
+					.add(
+						egui::DragValue::new(&mut self.tick_rate)
+							.clamp_range(1.0..=1000.0)
+							.suffix(" Hz"),
+					)
+					.changed()
+				{
+					changes.push(Change::Timestep(Timestep::Fixed(self.tick_rate)));
+				}
+			});
+		});
+		changes
+	}
+}
+
+impl Default for WindowPanel {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E: From<Error>> Plugin<E> for WindowPanel {
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let mut open = self.open;
+		let mut changes = vec![];
+		egui::Window::new("Window settings")
+			.open(&mut open)
+			.show(egui_ctx, |ui| changes = self.settings_ui(ctx, ui));
+		self.open = open;
+		for change in changes {
+			change.apply(ctx).map_err(Error::from)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,119 @@
+use std::time::Instant;
+
+use tetra::Event;
+
+use crate::Error;
+
+/// What a [`StateWrapper`](crate::StateWrapper) did with an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventDelivery {
+	/// The event was sent to the game's [`State`](crate::State).
+	Game,
+	/// The event was consumed by a [`Plugin`].
+	Plugin,
+	/// The event wasn't sent to the game because the GUI was using
+	/// the mouse or keyboard.
+	Gui,
+	/// The event wasn't sent to the game because it happened outside
+	/// of the game's [`Viewport`](crate::Viewport).
+	OutsideViewport,
+	/// The event was a press of a key that shows or hides a GUI.
+	Toggle,
+	/// The event wasn't sent to the game because gameplay was paused
+	/// with [`TimeControls`](crate::TimeControls). Releases of keys the
+	/// game saw pressed are still delivered.
+	Paused,
+}
+
+/// An event received by a [`StateWrapper`](crate::StateWrapper), along
+/// with when it was received and what was done with it.
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+	/// The event, as it was received from Tetra.
+	pub event: Event,
+	/// When the event was received.
+	pub time: Instant,
+	/// What was done with the event.
+	pub delivery: EventDelivery,
+}
+
+/// A reusable piece of UI or debugging functionality that can be
+/// added to a [`StateWrapper`](crate::StateWrapper) alongside the main
+/// [`State`](crate::State).
+///
+/// Plugins are run in the order they were added to the
+/// [`StateWrapper`](crate::StateWrapper):
+/// - [`event`](Plugin::event) is called **before** the main state's
+///   `event` callback, and a plugin can consume an event to stop it from
+///   reaching later plugins and the main state.
+/// - [`ui`](Plugin::ui), [`update`](Plugin::update), and
+///   [`draw`](Plugin::draw) are called **after** the main state's
+///   corresponding callbacks, so plugins can draw on top of the game.
+///
+/// Plugins that aren't tied to a specific game should be generic over
+/// the error type so they can be used with any state:
+///
+/// ```
+/// use egui_tetra::{egui, Plugin};
+///
+/// struct HelloPlugin;
+///
+/// impl<E: From<egui_tetra::Error>> Plugin<E> for HelloPlugin {
+/// 	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+/// 		egui::Window::new("Hello").show(egui_ctx, |ui| {
+/// 			ui.label("Hello from a plugin!");
+/// 		});
+/// 		Ok(())
+/// 	}
+/// }
+/// ```
+#[allow(unused_variables)]
+pub trait Plugin<E: From<Error> = Error> {
+	/// Called when it is time for the plugin to construct a GUI.
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when it is time for the plugin to update.
+	fn update(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when it is time for the plugin to be drawn.
+	///
+	/// Plugins are drawn after the main state, but before the GUI.
+	fn draw(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when a window or input event occurs.
+	///
+	/// Mouse and keyboard input events will not be received if the GUI
+	/// is using the mouse or keyboard, respectively.
+	///
+	/// Returns `true` if the plugin consumed the event, in which case
+	/// later plugins and the main state will not receive it.
+	fn event(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		event: &Event,
+	) -> Result<bool, E> {
+		Ok(false)
+	}
+
+	/// Called for every event the [`StateWrapper`](crate::StateWrapper)
+	/// receives once it's been delivered, including events that were used
+	/// by the GUI or consumed by a plugin.
+	///
+	/// This is useful for debugging tools that show why input did or
+	/// didn't reach the game.
+	fn inspect_event(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		record: &EventRecord,
+	) -> Result<(), E> {
+		Ok(())
+	}
+}
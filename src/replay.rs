@@ -0,0 +1,257 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tetra::{
+	input::{GamepadAxis, GamepadButton, GamepadStick, Key, MouseButton},
+	math::Vec2,
+	Event,
+};
+
+use crate::{logged, Error};
+
+/// A copy of a [`tetra::Event`] that can be serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SerializedEvent {
+	Resized {
+		width: i32,
+		height: i32,
+	},
+	Restored,
+	Minimized,
+	Maximized,
+	FocusGained,
+	FocusLost,
+	KeyPressed {
+		key: Key,
+	},
+	KeyReleased {
+		key: Key,
+	},
+	MouseButtonPressed {
+		button: MouseButton,
+	},
+	MouseButtonReleased {
+		button: MouseButton,
+	},
+	MouseMoved {
+		position: Vec2<f32>,
+		delta: Vec2<f32>,
+	},
+	MouseWheelMoved {
+		amount: Vec2<i32>,
+	},
+	GamepadAdded {
+		id: usize,
+	},
+	GamepadRemoved {
+		id: usize,
+	},
+	GamepadButtonPressed {
+		id: usize,
+		button: GamepadButton,
+	},
+	GamepadButtonReleased {
+		id: usize,
+		button: GamepadButton,
+	},
+	GamepadAxisMoved {
+		id: usize,
+		axis: GamepadAxis,
+		position: f32,
+	},
+	GamepadStickMoved {
+		id: usize,
+		stick: GamepadStick,
+		position: Vec2<f32>,
+	},
+	TextInput {
+		text: String,
+	},
+	FileDropped {
+		path: PathBuf,
+	},
+}
+
+impl SerializedEvent {
+	/// Copies a Tetra event, or returns `None` if the event is a
+	/// kind this version of egui-tetra doesn't know about.
+	fn new(event: &Event) -> Option<Self> {
+		Some(match event.clone() {
+			Event::Resized { width, height } => Self::Resized { width, height },
+			Event::Restored => Self::Restored,
+			Event::Minimized => Self::Minimized,
+			Event::Maximized => Self::Maximized,
+			Event::FocusGained => Self::FocusGained,
+			Event::FocusLost => Self::FocusLost,
+			Event::KeyPressed { key } => Self::KeyPressed { key },
+			Event::KeyReleased { key } => Self::KeyReleased { key },
+			Event::MouseButtonPressed { button } => Self::MouseButtonPressed { button },
+			Event::MouseButtonReleased { button } => Self::MouseButtonReleased { button },
+			Event::MouseMoved { position, delta } => Self::MouseMoved { position, delta },
+			Event::MouseWheelMoved { amount } => Self::MouseWheelMoved { amount },
+			Event::GamepadAdded { id } => Self::GamepadAdded { id },
+			Event::GamepadRemoved { id } => Self::GamepadRemoved { id },
+			Event::GamepadButtonPressed { id, button } => Self::GamepadButtonPressed { id, button },
+			Event::GamepadButtonReleased { id, button } => {
+				Self::GamepadButtonReleased { id, button }
+			}
+			Event::GamepadAxisMoved { id, axis, position } => {
+				Self::GamepadAxisMoved { id, axis, position }
+			}
+			Event::GamepadStickMoved {
+				id,
+				stick,
+				position,
+			} => Self::GamepadStickMoved {
+				id,
+				stick,
+				position,
+			},
+			Event::TextInput { text } => Self::TextInput { text },
+			Event::FileDropped { path } => Self::FileDropped { path },
+			_ => return None,
+		})
+	}
+
+	fn to_event(&self) -> Event {
+		match self.clone() {
+			Self::Resized { width, height } => Event::Resized { width, height },
+			Self::Restored => Event::Restored,
+			Self::Minimized => Event::Minimized,
+			Self::Maximized => Event::Maximized,
+			Self::FocusGained => Event::FocusGained,
+			Self::FocusLost => Event::FocusLost,
+			Self::KeyPressed { key } => Event::KeyPressed { key },
+			Self::KeyReleased { key } => Event::KeyReleased { key },
+			Self::MouseButtonPressed { button } => Event::MouseButtonPressed { button },
+			Self::MouseButtonReleased { button } => Event::MouseButtonReleased { button },
+			Self::MouseMoved { position, delta } => Event::MouseMoved { position, delta },
+			Self::MouseWheelMoved { amount } => Event::MouseWheelMoved { amount },
+			Self::GamepadAdded { id } => Event::GamepadAdded { id },
+			Self::GamepadRemoved { id } => Event::GamepadRemoved { id },
+			Self::GamepadButtonPressed { id, button } => Event::GamepadButtonPressed { id, button },
+			Self::GamepadButtonReleased { id, button } => {
+				Event::GamepadButtonReleased { id, button }
+			}
+			Self::GamepadAxisMoved { id, axis, position } => {
+				Event::GamepadAxisMoved { id, axis, position }
+			}
+			Self::GamepadStickMoved {
+				id,
+				stick,
+				position,
+			} => Event::GamepadStickMoved {
+				id,
+				stick,
+				position,
+			},
+			Self::TextInput { text } => Event::TextInput { text },
+			Self::FileDropped { path } => Event::FileDropped { path },
+		}
+	}
+}
+
+/// An event in an [`InputRecording`], along with when it was received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+	frame: u64,
+	tick: u64,
+	event: SerializedEvent,
+}
+
+impl RecordedEvent {
+	/// Returns the number of Tetra updates between the start of the
+	/// recording and the update the event was delivered in.
+	pub fn frame(&self) -> u64 {
+		self.frame
+	}
+
+	/// Returns the number of times the game had updated since the start
+	/// of the recording when the event was received.
+	///
+	/// This can be lower than [`frame`](RecordedEvent::frame) if gameplay
+	/// was paused or slowed down with [`TimeControls`](crate::TimeControls).
+	pub fn tick(&self) -> u64 {
+		self.tick
+	}
+
+	/// Returns the recorded event.
+	pub fn event(&self) -> Event {
+		self.event.to_event()
+	}
+}
+
+/// A list of events recorded by a [`StateWrapper`](crate::StateWrapper)
+/// that can be saved to a file and replayed later.
+///
+/// Recordings are made with [`StateWrapper::start_recording`](crate::StateWrapper::start_recording)
+/// and replayed with [`StateWrapper::start_replay`](crate::StateWrapper::start_replay).
+/// This requires the `replay` feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputRecording {
+	events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+	/// Creates a new, empty [`InputRecording`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Loads a recording from a file.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+		let recording = std::fs::read_to_string(path)
+			.map_err(|error| logged(Error::ReplayError(Box::new(error))))?;
+		ron::from_str(&recording).map_err(|error| logged(Error::ReplayError(Box::new(error))))
+	}
+
+	/// Saves the recording to a file.
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+		let recording =
+			ron::to_string(self).map_err(|error| logged(Error::ReplayError(Box::new(error))))?;
+		std::fs::write(path, recording).map_err(|error| logged(Error::ReplayError(Box::new(error))))
+	}
+
+	/// Returns the recorded events in the order they were received.
+	pub fn events(&self) -> &[RecordedEvent] {
+		&self.events
+	}
+
+	/// Returns the number of recorded events.
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	/// Returns `true` if no events have been recorded.
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+
+	/// Returns the number of Tetra updates the recording covers.
+	pub fn frames(&self) -> u64 {
+		self.events.last().map_or(0, |event| event.frame + 1)
+	}
+
+	pub(crate) fn push(&mut self, frame: u64, tick: u64, event: &Event) {
+		if let Some(event) = SerializedEvent::new(event) {
+			self.events.push(RecordedEvent { frame, tick, event });
+		}
+	}
+}
+
+/// Returns `true` if an event describes a change to the window, rather
+/// than input.
+///
+/// These events are passed through from the real window during a
+/// replay, and aren't replayed from the recording.
+pub(crate) fn is_window_event(event: &Event) -> bool {
+	matches!(
+		event,
+		Event::Resized { .. }
+			| Event::Restored
+			| Event::Minimized
+			| Event::Maximized
+			| Event::FocusGained
+			| Event::FocusLost
+	)
+}
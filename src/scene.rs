@@ -0,0 +1,439 @@
+use tetra::Event;
+
+use crate::{Error, State};
+
+/// A change to make to a [`SceneStack`], returned from [`Scene`] callbacks.
+///
+/// Transitions apply to the scene that returned them, which is the
+/// topmost scene unless a scene above it lets it keep running.
+pub enum Transition<E: From<Error> = Error> {
+	/// Leave the stack as it is.
+	None,
+	/// Put a new scene directly above the scene that returned
+	/// the transition.
+	Push(Box<dyn Scene<E>>),
+	/// Remove the scene that returned the transition from the stack.
+	///
+	/// If the last scene is removed, the game will quit.
+	Pop,
+	/// Replace the scene that returned the transition with a new scene.
+	Replace(Box<dyn Scene<E>>),
+}
+
+/// A single screen of a game, such as a menu, gameplay, or pause screen,
+/// that can be managed by a [`SceneStack`].
+///
+/// The callbacks are the same as [`State`]'s, except that most of them
+/// return a [`Transition`] to change which scenes are on the stack.
+///
+/// By default, a scene hides every scene below it. The
+/// [`update_below`](Scene::update_below), [`draw_below`](Scene::draw_below),
+/// [`ui_below`](Scene::ui_below), and [`events_below`](Scene::events_below)
+/// methods can be overridden to let lower scenes keep running, for
+/// example to draw gameplay behind a pause menu.
+#[allow(unused_variables)]
+pub trait Scene<E: From<Error> = Error> {
+	/// Called when it is time for the scene to construct a GUI.
+	fn ui(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+	) -> Result<Transition<E>, E> {
+		Ok(Transition::None)
+	}
+
+	/// Called when it is time for the scene to update.
+	fn update(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+	) -> Result<Transition<E>, E> {
+		Ok(Transition::None)
+	}
+
+	/// Called when it is time for the scene to be drawn.
+	fn draw(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when a window or input event occurs.
+	///
+	/// Mouse and keyboard input events will not be received if the GUI
+	/// is using the mouse or keyboard, respectively.
+	///
+	/// Returns a transition along with `true` if the scene consumed the
+	/// event, in which case the scenes below it will not receive it.
+	fn event(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		event: Event,
+	) -> Result<(Transition<E>, bool), E> {
+		Ok((Transition::None, false))
+	}
+
+	/// Whether the scene below this one should keep updating
+	/// while this scene is on the stack.
+	fn update_below(&self) -> bool {
+		false
+	}
+
+	/// Whether the scene below this one should keep being drawn
+	/// while this scene is on the stack.
+	fn draw_below(&self) -> bool {
+		false
+	}
+
+	/// Whether the scene below this one should keep constructing
+	/// a GUI while this scene is on the stack.
+	fn ui_below(&self) -> bool {
+		false
+	}
+
+	/// Whether the scene below this one should keep receiving
+	/// events while this scene is on the stack.
+	///
+	/// Events are always delivered to the topmost scene first, and
+	/// stop at the first scene that [consumes](Scene::event) them.
+	fn events_below(&self) -> bool {
+		false
+	}
+}
+
+/// A stack of [`Scene`]s that implements [`State`], so it can be
+/// wrapped in a [`StateWrapper`](crate::StateWrapper).
+///
+/// Only the topmost scene is guaranteed to run. Each scene decides
+/// whether the scene below it keeps updating, drawing, constructing
+/// a GUI, and receiving events.
+///
+/// ```no_run
+/// # use std::error::Error;
+/// use egui_tetra::{egui, Scene, SceneStack, StateWrapper, Transition};
+///
+/// struct Menu;
+///
+/// impl Scene<Box<dyn Error>> for Menu {
+/// 	fn ui(
+/// 		&mut self,
+/// 		ctx: &mut tetra::Context,
+/// 		egui_ctx: &egui::CtxRef,
+/// 	) -> Result<Transition<Box<dyn Error>>, Box<dyn Error>> {
+/// 		let mut transition = Transition::None;
+/// 		egui::Window::new("Menu").show(egui_ctx, |ui| {
+/// 			if ui.button("Quit").clicked() {
+/// 				transition = Transition::Pop;
+/// 			}
+/// 		});
+/// 		Ok(transition)
+/// 	}
+/// }
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// 	tetra::ContextBuilder::new("example", 800, 600)
+/// 		.build()?
+/// 		.run(|_| Ok(StateWrapper::new(SceneStack::new(Menu))))
+/// }
+/// ```
+pub struct SceneStack<E: From<Error> = Error> {
+	scenes: Vec<Box<dyn Scene<E>>>,
+}
+
+impl<E: From<Error>> SceneStack<E> {
+	/// Creates a new [`SceneStack`] with an initial scene.
+	pub fn new(scene: impl Scene<E> + 'static) -> Self {
+		Self {
+			scenes: vec![Box::new(scene)],
+		}
+	}
+
+	/// Puts a new scene on top of the stack.
+	pub fn push(&mut self, scene: impl Scene<E> + 'static) {
+		self.scenes.push(Box::new(scene));
+	}
+
+	/// Removes the topmost scene from the stack and returns it.
+	pub fn pop(&mut self) -> Option<Box<dyn Scene<E>>> {
+		self.scenes.pop()
+	}
+
+	/// Replaces the topmost scene with a new scene and returns
+	/// the old one.
+	pub fn replace(&mut self, scene: impl Scene<E> + 'static) -> Option<Box<dyn Scene<E>>> {
+		let old = self.scenes.pop();
+		self.scenes.push(Box::new(scene));
+		old
+	}
+
+	/// Returns the number of scenes on the stack.
+	pub fn len(&self) -> usize {
+		self.scenes.len()
+	}
+
+	/// Returns `true` if there are no scenes on the stack.
+	pub fn is_empty(&self) -> bool {
+		self.scenes.is_empty()
+	}
+
+	/// Returns the index of the lowest scene that should run,
+	/// given a function that says whether a scene lets the
+	/// scene below it run.
+	fn lowest_active(&self, runs_below: impl Fn(&dyn Scene<E>) -> bool) -> usize {
+		let mut lowest = self.scenes.len().saturating_sub(1);
+		while lowest > 0 && runs_below(self.scenes[lowest].as_ref()) {
+			lowest -= 1;
+		}
+		lowest
+	}
+
+	/// Applies transitions returned by the scenes at the given indices.
+	fn apply_transitions(&mut self, mut transitions: Vec<(usize, Transition<E>)>) {
+		// changing a scene only moves the scenes above it, so applying
+		// the transitions from the top down keeps the indices valid
+		transitions.sort_by(|(a, _), (b, _)| b.cmp(a));
+		for (index, transition) in transitions {
+			match transition {
+				Transition::None => {}
+				Transition::Push(scene) => self.scenes.insert(index + 1, scene),
+				Transition::Pop => {
+					self.scenes.remove(index);
+				}
+				Transition::Replace(scene) => self.scenes[index] = scene,
+			}
+		}
+	}
+
+	/// Calls `event` for each scene that should receive an event, from
+	/// the top down, until a scene consumes it, then applies the returned
+	/// transitions.
+	fn dispatch_event(
+		&mut self,
+		mut event: impl FnMut(usize, &mut dyn Scene<E>) -> Result<(Transition<E>, bool), E>,
+	) -> Result<(), E> {
+		let lowest = self.lowest_active(|scene| scene.events_below());
+		let mut transitions = vec![];
+		for (index, scene) in self.scenes.iter_mut().enumerate().skip(lowest).rev() {
+			let (transition, consumed) = event(index, scene.as_mut())?;
+			transitions.push((index, transition));
+			if consumed {
+				break;
+			}
+		}
+		self.apply_transitions(transitions);
+		Ok(())
+	}
+
+	fn quit_if_empty(&self, ctx: &mut tetra::Context) {
+		if self.scenes.is_empty() {
+			tetra::window::quit(ctx);
+		}
+	}
+}
+
+impl<E: From<Error>> State<E> for SceneStack<E> {
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let lowest = self.lowest_active(|scene| scene.ui_below());
+		let mut transitions = vec![];
+		for (index, scene) in self.scenes.iter_mut().enumerate().skip(lowest) {
+			transitions.push((index, scene.ui(ctx, egui_ctx)?));
+		}
+		self.apply_transitions(transitions);
+		self.quit_if_empty(ctx);
+		Ok(())
+	}
+
+	fn update(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let lowest = self.lowest_active(|scene| scene.update_below());
+		let mut transitions = vec![];
+		for (index, scene) in self.scenes.iter_mut().enumerate().skip(lowest) {
+			transitions.push((index, scene.update(ctx, egui_ctx)?));
+		}
+		self.apply_transitions(transitions);
+		self.quit_if_empty(ctx);
+		Ok(())
+	}
+
+	fn draw(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		let lowest = self.lowest_active(|scene| scene.draw_below());
+		for scene in self.scenes.iter_mut().skip(lowest) {
+			scene.draw(ctx, egui_ctx)?;
+		}
+		Ok(())
+	}
+
+	fn event(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		event: Event,
+	) -> Result<(), E> {
+		self.dispatch_event(|_, scene| scene.event(ctx, egui_ctx, event.clone()))?;
+		self.quit_if_empty(ctx);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// A scene that does nothing. Scenes are told apart by the
+	/// addresses of their boxes, so it isn't zero-sized.
+	struct TestScene(#[allow(dead_code)] u8);
+
+	impl Scene for TestScene {}
+
+	/// A scene that lets the scene below it receive events.
+	struct OverlayScene;
+
+	impl Scene for OverlayScene {
+		fn events_below(&self) -> bool {
+			true
+		}
+	}
+
+	fn scene() -> Box<dyn Scene> {
+		Box::new(TestScene(0))
+	}
+
+	fn address(scene: &dyn Scene) -> *const () {
+		scene as *const dyn Scene as *const ()
+	}
+
+	/// Returns the addresses of the scenes on a stack, from bottom to top.
+	fn addresses(stack: &SceneStack) -> Vec<*const ()> {
+		stack
+			.scenes
+			.iter()
+			.map(|scene| address(scene.as_ref()))
+			.collect()
+	}
+
+	/// Creates a stack with the given number of scenes, along with
+	/// their addresses.
+	fn stack(len: usize) -> (SceneStack, Vec<*const ()>) {
+		let stack = SceneStack {
+			scenes: (0..len).map(|_| scene()).collect(),
+		};
+		let addresses = addresses(&stack);
+		(stack, addresses)
+	}
+
+	#[test]
+	fn push_from_top() {
+		let (mut stack, scenes) = stack(2);
+		let pushed = scene();
+		let pushed_address = address(pushed.as_ref());
+		stack.apply_transitions(vec![(1, Transition::Push(pushed))]);
+		assert_eq!(
+			addresses(&stack),
+			vec![scenes[0], scenes[1], pushed_address]
+		);
+	}
+
+	#[test]
+	fn pop_from_top() {
+		let (mut stack, scenes) = stack(2);
+		stack.apply_transitions(vec![(1, Transition::Pop)]);
+		assert_eq!(addresses(&stack), vec![scenes[0]]);
+		stack.apply_transitions(vec![(0, Transition::Pop)]);
+		assert!(stack.is_empty());
+	}
+
+	#[test]
+	fn replace_top() {
+		let (mut stack, scenes) = stack(2);
+		let replacement = scene();
+		let replacement_address = address(replacement.as_ref());
+		stack.apply_transitions(vec![(1, Transition::Replace(replacement))]);
+		assert_eq!(addresses(&stack), vec![scenes[0], replacement_address]);
+	}
+
+	#[test]
+	fn pop_from_lower_scene() {
+		let (mut stack, scenes) = stack(3);
+		stack.apply_transitions(vec![(1, Transition::Pop)]);
+		assert_eq!(addresses(&stack), vec![scenes[0], scenes[2]]);
+	}
+
+	#[test]
+	fn replace_lower_scene() {
+		let (mut stack, scenes) = stack(2);
+		let replacement = scene();
+		let replacement_address = address(replacement.as_ref());
+		stack.apply_transitions(vec![(0, Transition::Replace(replacement))]);
+		assert_eq!(addresses(&stack), vec![replacement_address, scenes[1]]);
+	}
+
+	#[test]
+	fn push_from_lower_scene() {
+		let (mut stack, scenes) = stack(2);
+		let pushed = scene();
+		let pushed_address = address(pushed.as_ref());
+		stack.apply_transitions(vec![(0, Transition::Push(pushed))]);
+		assert_eq!(
+			addresses(&stack),
+			vec![scenes[0], pushed_address, scenes[1]]
+		);
+	}
+
+	#[test]
+	fn transitions_from_several_scenes() {
+		let (mut stack, scenes) = stack(3);
+		let pushed = scene();
+		let pushed_address = address(pushed.as_ref());
+		// in the order the scenes were updated, from the bottom up
+		stack.apply_transitions(vec![
+			(0, Transition::Push(pushed)),
+			(1, Transition::Pop),
+			(2, Transition::None),
+		]);
+		assert_eq!(
+			addresses(&stack),
+			vec![scenes[0], pushed_address, scenes[2]]
+		);
+	}
+
+	#[test]
+	fn events_stop_at_consuming_scene() {
+		let mut stack: SceneStack = SceneStack {
+			scenes: vec![
+				Box::new(OverlayScene),
+				Box::new(OverlayScene),
+				Box::new(OverlayScene),
+			],
+		};
+		let mut received = vec![];
+		stack
+			.dispatch_event(|index, _| {
+				received.push(index);
+				Ok((Transition::None, index == 1))
+			})
+			.unwrap();
+		assert_eq!(received, vec![2, 1]);
+	}
+
+	#[test]
+	fn events_stop_at_scene_hiding_those_below() {
+		let (mut stack, _) = stack(2);
+		let mut received = vec![];
+		stack
+			.dispatch_event(|index, _| {
+				received.push(index);
+				Ok((Transition::None, false))
+			})
+			.unwrap();
+		assert_eq!(received, vec![1]);
+	}
+
+	#[test]
+	fn transition_from_consuming_scene() {
+		let mut stack: SceneStack = SceneStack {
+			scenes: vec![Box::new(OverlayScene), Box::new(OverlayScene)],
+		};
+		stack
+			.dispatch_event(|index, _| Ok((Transition::Pop, index == 1)))
+			.unwrap();
+		assert_eq!(stack.len(), 1);
+	}
+}
@@ -0,0 +1,454 @@
+use std::collections::VecDeque;
+
+use tetra::{
+	input::{Key, MouseButton},
+	math::Vec2,
+	Event,
+};
+
+use crate::{logged, EguiWrapper, Error};
+
+const DEFAULT_TIMEOUT: u32 = 60;
+
+/// A widget registered with [`ScriptTarget::script_target`].
+#[derive(Debug, Clone)]
+pub(crate) struct Target {
+	pub(crate) id: egui::Id,
+	pub(crate) rect: egui::Rect,
+	pub(crate) label: String,
+}
+
+/// The widgets registered during the current frame.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Targets(pub(crate) Vec<Target>);
+
+impl Targets {
+	pub(crate) fn id() -> egui::Id {
+		egui::Id::new("egui_tetra_script_targets")
+	}
+}
+
+/// Lets a [`UiScript`] find a widget by a label, using the widget's
+/// [`Response`](egui::Response).
+///
+/// ```
+/// # fn ui(ui: &mut egui_tetra::egui::Ui) {
+/// use egui_tetra::ScriptTarget;
+///
+/// if ui.button("▶ Play").script_target("Play").clicked() {
+/// 	// start the game
+/// }
+/// # }
+/// ```
+pub trait ScriptTarget {
+	/// Registers the widget under `label` for the current frame and
+	/// returns it.
+	fn script_target(self, label: impl Into<String>) -> Self;
+}
+
+impl ScriptTarget for egui::Response {
+	fn script_target(self, label: impl Into<String>) -> Self {
+		let target = Target {
+			id: self.id,
+			rect: self.rect,
+			label: label.into(),
+		};
+		{
+			let mut memory = self.ctx.memory();
+			let targets = &mut memory
+				.data
+				.get_temp_mut_or_default::<Targets>(Targets::id())
+				.0;
+			match targets.iter_mut().find(|existing| existing.id == target.id) {
+				Some(existing) => *existing = target,
+				None => targets.push(target),
+			}
+		}
+		self
+	}
+}
+
+#[derive(Debug, Clone)]
+enum Action {
+	Click(String),
+	TypeText(String),
+	PressKey(Key),
+	Wait(u32),
+	Quit,
+}
+
+/// A list of high-level GUI actions, such as clicking a widget with a
+/// certain label or typing text, that are turned into Tetra input events
+/// one frame at a time.
+///
+/// This is useful for testing menus and other GUIs from start to finish
+/// without working out mouse coordinates by hand. The events go through
+/// the same path as real input, so they reach the GUI (and the game, when
+/// the script is run by a [`StateWrapper`](crate::StateWrapper)) like
+/// the real thing.
+///
+/// Widgets are found by label in the previous frame. A click aims at the
+/// center of the widget registered under the label with
+/// [`ScriptTarget::script_target`], or if there isn't one, at the center
+/// of the text with the label (see [`EguiWrapper::find_text`]). Text works
+/// for buttons, checkboxes, selectable labels, menus, and other widgets
+/// that can be clicked anywhere on their text, while registering a widget
+/// also works for widgets with icons, hint text, or labels split into
+/// several pieces of text.
+///
+/// If the label can't be found, the script waits for it to appear, and
+/// returns [`Error::ScriptError`] if it doesn't appear before the
+/// [timeout](UiScript::timeout). It also returns an error if more than one
+/// widget (or, without a registered widget, more than one piece of text)
+/// has the label, rather than guessing which one to click.
+///
+/// The easiest way to run a script is with [`StateWrapper::with_ui_script`](crate::StateWrapper::with_ui_script).
+/// Scripts can also be run manually by passing the events returned by
+/// [`next_events`](UiScript::next_events) to [`EguiWrapper::event`] before
+/// beginning each frame:
+///
+/// ```
+/// # fn update(
+/// # 	ctx: &mut tetra::Context,
+/// # 	egui: &mut egui_tetra::EguiWrapper,
+/// # 	script: &mut egui_tetra::UiScript,
+/// # ) -> Result<(), egui_tetra::Error> {
+/// for event in script.next_events(ctx, egui)? {
+/// 	egui.event(ctx, &event)?;
+/// }
+/// egui.begin_frame(ctx)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A script for a main menu might look like this:
+///
+/// ```
+/// use egui_tetra::UiScript;
+///
+/// let script = UiScript::new()
+/// 	.click("New game")
+/// 	// clicking a text field's hint text focuses the field
+/// 	.click("Enter your name")
+/// 	.type_text("Tester")
+/// 	.press_enter()
+/// 	.wait(10)
+/// 	.quit();
+/// ```
+#[derive(Debug, Clone)]
+pub struct UiScript {
+	actions: VecDeque<Action>,
+	timeout: u32,
+	/// The number of frames the current action has been running for.
+	frame: u32,
+	/// The number of frames the current click has waited for its
+	/// label to appear.
+	waited: u32,
+}
+
+impl UiScript {
+	/// Creates a new [`UiScript`] with no actions.
+	pub fn new() -> Self {
+		Self {
+			actions: VecDeque::new(),
+			timeout: DEFAULT_TIMEOUT,
+			frame: 0,
+			waited: 0,
+		}
+	}
+
+	/// Sets how many frames to wait for a label to appear before
+	/// giving up and returns the script.
+	///
+	/// Defaults to 60.
+	pub fn timeout(mut self, frames: u32) -> Self {
+		self.timeout = frames;
+		self
+	}
+
+	/// Adds an action that moves the mouse to the text `label` and clicks
+	/// the left mouse button, and returns the script.
+	pub fn click(mut self, label: impl Into<String>) -> Self {
+		self.actions.push_back(Action::Click(label.into()));
+		self
+	}
+
+	/// Adds an action that types text into the focused widget and
+	/// returns the script.
+	pub fn type_text(mut self, text: impl Into<String>) -> Self {
+		self.actions.push_back(Action::TypeText(text.into()));
+		self
+	}
+
+	/// Adds an action that presses and releases a key and returns
+	/// the script.
+	pub fn press_key(mut self, key: Key) -> Self {
+		self.actions.push_back(Action::PressKey(key));
+		self
+	}
+
+	/// Adds an action that presses and releases the Enter key and returns
+	/// the script.
+	pub fn press_enter(self) -> Self {
+		self.press_key(Key::Enter)
+	}
+
+	/// Adds an action that does nothing for a number of frames and
+	/// returns the script.
+	pub fn wait(mut self, frames: u32) -> Self {
+		self.actions.push_back(Action::Wait(frames));
+		self
+	}
+
+	/// Adds an action that [quits](tetra::window::quit) the game and
+	/// returns the script.
+	pub fn quit(mut self) -> Self {
+		self.actions.push_back(Action::Quit);
+		self
+	}
+
+	/// Returns `true` if every action has been performed.
+	pub fn is_finished(&self) -> bool {
+		self.actions.is_empty()
+	}
+
+	/// Returns the events for the current frame of the script.
+	///
+	/// This should be called once per frame, before the GUI's frame begins,
+	/// with the [`EguiWrapper`] the events will be sent to.
+	pub fn next_events(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui: &EguiWrapper,
+	) -> Result<Vec<Event>, Error> {
+		let (events, quit) = self.advance(egui)?;
+		if quit {
+			tetra::window::quit(ctx);
+		}
+		Ok(events)
+	}
+
+	/// Returns the events for the current frame of the script, and
+	/// whether the game should quit.
+	fn advance(&mut self, egui: &EguiWrapper) -> Result<(Vec<Event>, bool), Error> {
+		let action = match self.actions.front() {
+			Some(action) => action,
+			None => return Ok((vec![], false)),
+		};
+		let mut events = vec![];
+		let mut quit = false;
+		let finished = match (action, self.frame) {
+			(Action::Click(label), 0) => match find_label(egui, label)? {
+				Some(rect) => {
+					let position = egui.gui_to_window(rect.center());
+					events.push(Event::MouseMoved {
+						position,
+						delta: egui
+							.pointer_position
+							.map_or_else(Vec2::zero, |previous| position - previous),
+					});
+					false
+				}
+				None if self.waited >= self.timeout => {
+					return Err(logged(Error::ScriptError(format!(
+						"couldn't find a widget labelled {:?}",
+						label
+					))));
+				}
+				None => {
+					self.waited += 1;
+					return Ok((events, false));
+				}
+			},
+			(Action::Click(_), 1) => {
+				events.push(Event::MouseButtonPressed {
+					button: MouseButton::Left,
+				});
+				false
+			}
+			(Action::Click(_), _) => {
+				events.push(Event::MouseButtonReleased {
+					button: MouseButton::Left,
+				});
+				true
+			}
+			(Action::TypeText(text), _) => {
+				events.push(Event::TextInput { text: text.clone() });
+				true
+			}
+			(Action::PressKey(key), 0) => {
+				events.push(Event::KeyPressed { key: *key });
+				false
+			}
+			(Action::PressKey(key), _) => {
+				events.push(Event::KeyReleased { key: *key });
+				true
+			}
+			(Action::Wait(frames), frame) => frame + 1 >= *frames,
+			(Action::Quit, _) => {
+				quit = true;
+				true
+			}
+		};
+		if finished {
+			self.actions.pop_front();
+			self.frame = 0;
+			self.waited = 0;
+		} else {
+			self.frame += 1;
+		}
+		Ok((events, quit))
+	}
+}
+
+impl Default for UiScript {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns the rectangle of the widget with the given label in the
+/// latest finished frame, or an error if more than one widget has it.
+fn find_label(egui: &EguiWrapper, label: &str) -> Result<Option<egui::Rect>, Error> {
+	let targets: Vec<egui::Rect> = egui
+		.script_targets
+		.iter()
+		.filter(|target| target.label == label)
+		.map(|target| target.rect)
+		.collect();
+	let rects = if targets.is_empty() {
+		egui.find_texts(label)
+	} else {
+		targets
+	};
+	match rects.as_slice() {
+		[] => Ok(None),
+		[rect] => Ok(Some(*rect)),
+		_ => Err(logged(Error::ScriptError(format!(
+			"found {} widgets labelled {:?}",
+			rects.len(),
+			label
+		)))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Runs a GUI frame through the wrapper without a Tetra context.
+	fn frame(egui: &mut EguiWrapper, add_contents: impl FnOnce(&mut egui::Ui)) {
+		egui.ctx.begin_frame(egui::RawInput {
+			screen_rect: Some(egui::Rect::from_min_size(
+				egui::Pos2::ZERO,
+				egui::vec2(800.0, 600.0),
+			)),
+			..Default::default()
+		});
+		egui::CentralPanel::default().show(&egui.ctx, add_contents);
+		let (_, shapes) = egui.ctx.end_frame();
+		egui.record_frame(&shapes);
+	}
+
+	fn events(script: &mut UiScript, egui: &EguiWrapper) -> Vec<Event> {
+		let (events, quit) = script.advance(egui).unwrap();
+		assert!(!quit);
+		events
+	}
+
+	fn script_error(script: &mut UiScript, egui: &EguiWrapper) -> String {
+		match script.advance(egui) {
+			Err(Error::ScriptError(message)) => message,
+			result => panic!("expected a script error, got {:?}", result),
+		}
+	}
+
+	#[test]
+	fn click_and_press_key() {
+		let mut egui = EguiWrapper::new();
+		let mut rect = egui::Rect::NOTHING;
+		frame(&mut egui, |ui| {
+			ui.label("Main menu");
+			rect = ui.button("▶ Play").script_target("Play").rect;
+		});
+		let mut script = UiScript::new().click("Play").press_enter();
+		match events(&mut script, &egui).as_slice() {
+			[Event::MouseMoved { position, .. }] => {
+				assert_eq!(*position, Vec2::new(rect.center().x, rect.center().y));
+			}
+			events => panic!("expected a mouse movement, got {:?}", events),
+		}
+		assert!(matches!(
+			events(&mut script, &egui).as_slice(),
+			[Event::MouseButtonPressed {
+				button: MouseButton::Left
+			}]
+		));
+		assert!(matches!(
+			events(&mut script, &egui).as_slice(),
+			[Event::MouseButtonReleased {
+				button: MouseButton::Left
+			}]
+		));
+		assert!(matches!(
+			events(&mut script, &egui).as_slice(),
+			[Event::KeyPressed { key: Key::Enter }]
+		));
+		assert!(matches!(
+			events(&mut script, &egui).as_slice(),
+			[Event::KeyReleased { key: Key::Enter }]
+		));
+		assert!(script.is_finished());
+	}
+
+	#[test]
+	fn click_falls_back_to_text() {
+		let mut egui = EguiWrapper::new();
+		frame(&mut egui, |ui| {
+			ui.label("Options");
+		});
+		let mut script = UiScript::new().click("Options");
+		assert!(matches!(
+			events(&mut script, &egui).as_slice(),
+			[Event::MouseMoved { .. }]
+		));
+	}
+
+	#[test]
+	fn duplicate_labels() {
+		let mut egui = EguiWrapper::new();
+		frame(&mut egui, |ui| {
+			ui.label("Ok");
+			ui.label("Ok");
+		});
+		let mut script = UiScript::new().click("Ok");
+		assert_eq!(
+			script_error(&mut script, &egui),
+			"found 2 widgets labelled \"Ok\""
+		);
+	}
+
+	#[test]
+	fn waits_for_label() {
+		let mut egui = EguiWrapper::new();
+		frame(&mut egui, |_| {});
+		let mut script = UiScript::new().timeout(2).click("Play");
+		assert!(events(&mut script, &egui).is_empty());
+		assert!(events(&mut script, &egui).is_empty());
+		assert_eq!(
+			script_error(&mut script, &egui),
+			"couldn't find a widget labelled \"Play\""
+		);
+	}
+
+	#[test]
+	fn targets_last_one_frame() {
+		let mut egui = EguiWrapper::new();
+		frame(&mut egui, |ui| {
+			ui.button("▶ Play").script_target("Play");
+		});
+		assert_eq!(egui.script_targets.len(), 1);
+		frame(&mut egui, |_| {});
+		assert!(egui.script_targets.is_empty());
+	}
+}
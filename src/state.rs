@@ -0,0 +1,815 @@
+use std::{collections::HashSet, ops::Bound, time::Instant};
+
+use egui::Order;
+use tetra::{graphics, input::Key, Event};
+
+#[cfg(feature = "replay")]
+use crate::{replay, InputRecording};
+use crate::{
+	Error, EventDelivery, EventRecord, NamedContext, Plugin, TimeControls, UiScript, Viewport,
+	MAIN_CONTEXT, UI_ENABLED,
+};
+
+/// The index of the main context in a [`StateWrapper`]'s contexts.
+const MAIN: usize = 0;
+
+/// A trait analogous to [`tetra::State`], but with the addition of a
+/// [`ui`](State::ui) callback and an `egui_ctx` argument in the
+/// other callbacks.
+///
+/// You can use a type implementing this trait as your main game
+/// state by wrapping it with a [`StateWrapper`] and passing the wrapper
+/// to [`tetra::Context::run`].
+#[allow(unused_variables)]
+pub trait State<E: From<Error> = Error> {
+	/// Called when it is time for the game to construct a GUI.
+	fn ui(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when it is time for the game to construct a GUI in an
+	/// additional context added with [`StateWrapper::add_context`].
+	fn context_ui(
+		&mut self,
+		ctx: &mut tetra::Context,
+		name: &str,
+		egui_ctx: &egui::CtxRef,
+	) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when it is time for the game to update.
+	///
+	/// When the game is wrapped in a [`StateWrapper`], this can be skipped
+	/// or called more than once per Tetra update if gameplay is paused or
+	/// its speed is changed with [`TimeControls`].
+	fn update(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when it is time for the game to be drawn.
+	fn draw(&mut self, ctx: &mut tetra::Context, egui_ctx: &egui::CtxRef) -> Result<(), E> {
+		Ok(())
+	}
+
+	/// Called when a window or input event occurs.
+	///
+	/// Mouse and keyboard input events will not be received if the GUI
+	/// is using the mouse or keyboard, respectively. Text input isn't
+	/// received while the GUI is using the keyboard either, but the release
+	/// of a key is always received if the press was, so keys don't get
+	/// stuck down when a text field takes the keyboard.
+	fn event(
+		&mut self,
+		ctx: &mut tetra::Context,
+		egui_ctx: &egui::CtxRef,
+		event: Event,
+	) -> Result<(), E> {
+		Ok(())
+	}
+}
+
+struct PluginEntry<E: From<Error>> {
+	context: String,
+	plugin: Box<dyn Plugin<E>>,
+}
+
+/// An adaptor that implements [`tetra::State`] for implementors of
+/// [`State`].
+pub struct StateWrapper<E: From<Error>> {
+	/// Events waiting to be delivered, along with when they were received.
+	events: Vec<(Instant, tetra::Event)>,
+	state: Box<dyn State<E>>,
+	plugins: Vec<PluginEntry<E>>,
+	/// The egui contexts, with the main context first.
+	contexts: Vec<NamedContext>,
+	/// Indices into `contexts`, sorted from lowest to highest priority.
+	context_order: Vec<usize>,
+	game_above: Option<Order>,
+	viewport: Option<Viewport>,
+	ui_toggle_key: Option<Key>,
+	ui_visible: bool,
+	ui_script: Option<UiScript>,
+	/// Keys the game has received a press for but not a release.
+	keys_down: HashSet<Key>,
+	/// The number of times `update` has been called.
+	#[cfg(feature = "replay")]
+	frame: u64,
+	#[cfg(feature = "replay")]
+	recording: Option<ActiveRecording>,
+	#[cfg(feature = "replay")]
+	replay: Option<ActiveReplay>,
+}
+
+/// An [`InputRecording`] in progress, along with the frame and tick
+/// it started on.
+#[cfg(feature = "replay")]
+struct ActiveRecording {
+	recording: InputRecording,
+	start_frame: u64,
+	start_tick: u64,
+}
+
+/// An [`InputRecording`] being replayed.
+#[cfg(feature = "replay")]
+struct ActiveReplay {
+	recording: InputRecording,
+	/// The index of the next event to replay.
+	next: usize,
+	start_frame: u64,
+}
+
+impl<E: From<Error>> StateWrapper<E> {
+	/// Wraps an implementor of [`State`] so it implements [`tetra::State`].
+	pub fn new(state: impl State<E> + 'static) -> Self {
+		Self {
+			events: vec![],
+			state: Box::new(state),
+			plugins: vec![],
+			contexts: vec![NamedContext::new(MAIN_CONTEXT)],
+			context_order: vec![MAIN],
+			game_above: None,
+			viewport: None,
+			ui_toggle_key: None,
+			ui_visible: true,
+			ui_script: None,
+			keys_down: HashSet::new(),
+			#[cfg(feature = "replay")]
+			frame: 0,
+			#[cfg(feature = "replay")]
+			recording: None,
+			#[cfg(feature = "replay")]
+			replay: None,
+		}
+	}
+
+	/// Draws the game to the given canvas and shows it in a [`Viewport`]
+	/// that fills the space left over by the GUI's panels, and returns
+	/// the wrapper.
+	///
+	/// Input events over the viewport are converted to game coordinates
+	/// before being sent to the state (see [`Viewport`] for details).
+	/// While the game is drawn to a viewport, the main context can't
+	/// be hidden.
+	pub fn with_viewport(mut self, canvas: tetra::graphics::Canvas) -> Self {
+		self.viewport = Some(Viewport::new(&mut self.contexts[MAIN].egui, canvas));
+		self
+	}
+
+	/// Returns the [`Viewport`] the game is drawn to, if there is one.
+	pub fn viewport(&self) -> Option<&Viewport> {
+		self.viewport.as_ref()
+	}
+
+	/// Draws the game above the GUI layers with the given [`Order`]
+	/// or lower and below the rest of the GUI, instead of below the
+	/// whole GUI, and returns the wrapper.
+	///
+	/// For example, using [`Order::Background`] will draw panels
+	/// (including a [`CentralPanel`](egui::CentralPanel)) behind the game
+	/// and windows in front of it. Since part of the GUI is drawn first,
+	/// the game shouldn't clear the screen in its [`draw`](State::draw)
+	/// callback.
+	///
+	/// This only affects the main context, and it has no effect if the
+	/// game is drawn to a [`Viewport`].
+	pub fn draw_game_above(mut self, order: Order) -> Self {
+		self.game_above = Some(order);
+		self
+	}
+
+	/// Sets a key that shows and hides the whole GUI, including every
+	/// context, and returns the wrapper.
+	///
+	/// While the GUI is hidden, [`State::ui`] and the other GUI callbacks
+	/// aren't called and all input is passed straight to the game. If the
+	/// game is drawn to a [`Viewport`], it's drawn to fill the window instead.
+	/// Presses of this key are not passed on to the GUI or the game.
+	pub fn with_ui_toggle_key(mut self, key: Key) -> Self {
+		self.ui_toggle_key = Some(key);
+		self
+	}
+
+	/// Returns `true` if the GUI is currently shown.
+	///
+	/// This is always `false` if [`UI_ENABLED`] is `false`.
+	pub fn is_ui_visible(&self) -> bool {
+		UI_ENABLED && self.ui_visible
+	}
+
+	/// Shows or hides the whole GUI.
+	pub fn set_ui_visible(&mut self, visible: bool) {
+		self.ui_visible = visible;
+	}
+
+	/// Runs a [`UiScript`] against the main context and returns the
+	/// wrapper.
+	///
+	/// The script's events are handled like real input events, so they
+	/// reach both the GUI and the game.
+	pub fn with_ui_script(mut self, script: UiScript) -> Self {
+		self.set_ui_script(Some(script));
+		self
+	}
+
+	/// Sets the [`UiScript`] to run against the main context, or `None`
+	/// to stop running a script.
+	pub fn set_ui_script(&mut self, script: Option<UiScript>) {
+		self.ui_script = script;
+	}
+
+	/// Returns the [`UiScript`] being run, if there is one.
+	pub fn ui_script(&self) -> Option<&UiScript> {
+		self.ui_script.as_ref()
+	}
+
+	/// Adds a [`Plugin`] to this wrapper and returns the wrapper.
+	///
+	/// Plugins run in the order they were added.
+	pub fn with_plugin(mut self, plugin: impl Plugin<E> + 'static) -> Self {
+		self.add_plugin(plugin);
+		self
+	}
+
+	/// Adds a [`Plugin`] to this wrapper.
+	///
+	/// Plugins run in the order they were added.
+	pub fn add_plugin(&mut self, plugin: impl Plugin<E> + 'static) {
+		self.add_plugin_to_context(MAIN_CONTEXT, plugin);
+	}
+
+	/// Adds a [`Plugin`] that uses the context with the given name
+	/// to this wrapper.
+	///
+	/// The plugin's [`ui`](Plugin::ui) callback is only called while
+	/// the context is visible. If there's no context with the given name,
+	/// the main context is used for the plugin's other callbacks.
+	pub fn add_plugin_to_context(
+		&mut self,
+		context_name: impl Into<String>,
+		plugin: impl Plugin<E> + 'static,
+	) {
+		self.plugins.push(PluginEntry {
+			context: context_name.into(),
+			plugin: Box::new(plugin),
+		});
+	}
+
+	/// Adds an additional egui context to this wrapper and returns
+	/// the wrapper.
+	///
+	/// If a context with the same name already exists, it is replaced
+	/// (see [`add_context`](Self::add_context)).
+	pub fn with_context(mut self, context: NamedContext) -> Self {
+		self.add_context(context);
+		self
+	}
+
+	/// Adds an additional egui context to this wrapper.
+	///
+	/// If a context with the same name already exists, it is replaced,
+	/// and textures registered with the old context are dropped along
+	/// with it. If the main context is replaced, the [`Viewport`]'s
+	/// texture is registered with the new context.
+	/// If [`UI_ENABLED`] is `false`, the context is discarded.
+	pub fn add_context(&mut self, mut context: NamedContext) {
+		if !UI_ENABLED {
+			return;
+		}
+		match self
+			.contexts
+			.iter()
+			.position(|existing| existing.name == context.name)
+		{
+			Some(index) => {
+				if index == MAIN {
+					if let Some(viewport) = &mut self.viewport {
+						viewport.register_texture(&mut context.egui);
+					}
+				}
+				self.contexts[index] = context;
+			}
+			None => self.contexts.push(context),
+		}
+		let contexts = &self.contexts;
+		self.context_order = (0..contexts.len()).collect();
+		self.context_order
+			.sort_by_key(|index| contexts[*index].priority);
+	}
+
+	/// Returns the context with the given name, if it exists.
+	pub fn context(&self, name: &str) -> Option<&NamedContext> {
+		self.contexts.iter().find(|context| context.name == name)
+	}
+
+	/// Returns a mutable reference to the context with the given name,
+	/// if it exists.
+	pub fn context_mut(&mut self, name: &str) -> Option<&mut NamedContext> {
+		self.contexts
+			.iter_mut()
+			.find(|context| context.name == name)
+	}
+
+	/// Returns a reference to this wrapper's main egui context.
+	pub fn ctx(&self) -> &egui::CtxRef {
+		self.contexts[MAIN].egui.ctx()
+	}
+
+	/// Saves the memory of every context that has a persistence file.
+	///
+	/// This happens automatically when the wrapper is dropped.
+	#[cfg(feature = "persistence")]
+	pub fn save_contexts(&self) -> Result<(), Error> {
+		for context in &self.contexts {
+			context.save_memory()?;
+		}
+		Ok(())
+	}
+
+	/// Starts recording every event the wrapper receives, discarding
+	/// any recording already in progress.
+	///
+	/// Each event is recorded with the frame it was delivered in and the
+	/// number of gameplay ticks so far, both counted from the start of
+	/// the recording.
+	#[cfg(feature = "replay")]
+	pub fn start_recording(&mut self) {
+		self.recording = Some(ActiveRecording {
+			recording: InputRecording::new(),
+			start_frame: self.frame,
+			start_tick: TimeControls::get(self.ctx()).ticks(),
+		});
+	}
+
+	/// Stops recording events and returns the recording, or `None` if
+	/// events weren't being recorded.
+	#[cfg(feature = "replay")]
+	pub fn stop_recording(&mut self) -> Option<InputRecording> {
+		self.recording.take().map(|recording| recording.recording)
+	}
+
+	/// Returns `true` if events are being recorded.
+	#[cfg(feature = "replay")]
+	pub fn is_recording(&self) -> bool {
+		self.recording.is_some()
+	}
+
+	/// Replays a recording from the next frame on, and returns the wrapper.
+	///
+	/// See [`start_replay`](StateWrapper::start_replay) for details.
+	#[cfg(feature = "replay")]
+	pub fn with_replay(mut self, recording: InputRecording) -> Self {
+		self.start_replay(recording);
+		self
+	}
+
+	/// Replays a recording from the next frame on, in place of live input.
+	///
+	/// Each recorded event is delivered on the same frame (counted from the
+	/// start of the replay) as it was during the recording, and goes through
+	/// the same path as a live event, so it reaches both the GUI and the game.
+	/// Live input events are ignored until the replay finishes. Window events,
+	/// such as resizing and focus changes, aren't replayed, and live window
+	/// events are still delivered.
+	///
+	/// The replay only affects the events the GUI and the game receive.
+	/// Functions that read the state of input devices, like
+	/// [`tetra::input::is_key_down`], still read the real devices.
+	#[cfg(feature = "replay")]
+	pub fn start_replay(&mut self, recording: InputRecording) {
+		self.replay = Some(ActiveReplay {
+			recording,
+			next: 0,
+			start_frame: self.frame,
+		});
+	}
+
+	/// Stops replaying a recording.
+	#[cfg(feature = "replay")]
+	pub fn stop_replay(&mut self) {
+		self.replay = None;
+	}
+
+	/// Returns `true` if a recording is being replayed.
+	#[cfg(feature = "replay")]
+	pub fn is_replaying(&self) -> bool {
+		self.replay.is_some()
+	}
+
+	/// Delivers the events from the replay that are due this frame.
+	#[cfg(feature = "replay")]
+	fn replay_events(&mut self, ctx: &mut tetra::Context) -> Result<(), E> {
+		let replay = match &mut self.replay {
+			Some(replay) => replay,
+			None => return Ok(()),
+		};
+		let frame = self.frame - replay.start_frame;
+		let mut events = vec![];
+		while let Some(recorded) = replay.recording.events().get(replay.next) {
+			if recorded.frame() > frame {
+				break;
+			}
+			events.push(recorded.event());
+			replay.next += 1;
+		}
+		if replay.next >= replay.recording.len() {
+			self.replay = None;
+		}
+		for event in events {
+			if !replay::is_window_event(&event) {
+				self.receive_event(ctx, event)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Sends an event to the GUI and queues it up to be delivered
+	/// to plugins and the game.
+	fn receive_event(&mut self, ctx: &mut tetra::Context, event: Event) -> Result<(), E> {
+		#[cfg(feature = "replay")]
+		if let Some(recording) = &mut self.recording {
+			let frame = self.frame - recording.start_frame;
+			let tick =
+				TimeControls::get(self.contexts[MAIN].egui.ctx()).ticks() - recording.start_tick;
+			recording.recording.push(frame, tick, &event);
+		}
+		self.update_modifiers(&event);
+		let time = Instant::now();
+		if let Event::KeyPressed { key } = &event {
+			if UI_ENABLED && self.ui_toggle_key == Some(*key) {
+				self.ui_visible = !self.ui_visible;
+				return self.inspect_event(
+					ctx,
+					EventRecord {
+						event,
+						time,
+						delivery: EventDelivery::Toggle,
+					},
+				);
+			}
+		}
+		if !self.is_ui_visible() {
+			self.events.push((time, event));
+			return Ok(());
+		}
+		if let Event::KeyPressed { key } = &event {
+			let mut toggled = false;
+			for (index, context) in self.contexts.iter_mut().enumerate() {
+				if context.toggle_key != Some(*key) || (index == MAIN && self.viewport.is_some()) {
+					continue;
+				}
+				context.visible = !context.visible;
+				toggled = true;
+			}
+			if toggled {
+				return self.inspect_event(
+					ctx,
+					EventRecord {
+						event,
+						time,
+						delivery: EventDelivery::Toggle,
+					},
+				);
+			}
+		}
+		for &index in self.context_order.iter().rev() {
+			let context = &mut self.contexts[index];
+			if !context.visible {
+				continue;
+			}
+			context.egui.event(ctx, &event)?;
+			if gui_blocks_event(context.egui.ctx(), &event) {
+				break;
+			}
+		}
+		self.events.push((time, event));
+		Ok(())
+	}
+
+	/// Updates the modifiers held in every context, including hidden ones
+	/// and ones a higher priority context blocks input to, so a modifier
+	/// released while a context isn't receiving input doesn't stay held
+	/// when it starts receiving input again.
+	fn update_modifiers(&mut self, event: &Event) {
+		for context in &mut self.contexts {
+			context.egui.update_modifiers(event);
+		}
+	}
+
+	/// [`inspect_event`](Plugin::inspect_event) callback.
+	fn inspect_event(&mut self, ctx: &mut tetra::Context, record: EventRecord) -> Result<(), E> {
+		for entry in &mut self.plugins {
+			let egui_ctx = context_ctx(&self.contexts, &entry.context);
+			entry.plugin.inspect_event(ctx, &egui_ctx, &record)?;
+		}
+		Ok(())
+	}
+
+	/// Returns `true` if a visible context is using the input device
+	/// that produced `event`.
+	fn gui_wants_event(&self, event: &Event) -> bool {
+		self.contexts
+			.iter()
+			.enumerate()
+			// when using a viewport, the viewport decides which events
+			// the main context lets through
+			.filter(|(index, _)| !(*index == MAIN && self.viewport.is_some()))
+			.any(|(_, context)| context.visible && gui_wants_event(context.egui.ctx(), event))
+	}
+}
+
+#[cfg(feature = "persistence")]
+impl<E: From<Error>> Drop for StateWrapper<E> {
+	fn drop(&mut self) {
+		// there's nowhere to report errors at this point, so saving
+		// is best-effort (failures are still logged if the `log`
+		// feature is enabled)
+		self.save_contexts().ok();
+	}
+}
+
+/// Returns the egui context with the given name, or the main
+/// context if there's no context with that name.
+fn context_ctx(contexts: &[NamedContext], name: &str) -> egui::CtxRef {
+	contexts
+		.iter()
+		.find(|context| context.name == name)
+		.unwrap_or(&contexts[MAIN])
+		.egui
+		.ctx()
+		.clone()
+}
+
+/// Returns `true` if gameplay is paused with [`TimeControls`] and won't
+/// update this frame, meaning events shouldn't be passed on to it.
+fn gameplay_stopped(egui_ctx: &egui::CtxRef) -> bool {
+	UI_ENABLED && TimeControls::get(egui_ctx).is_stopped()
+}
+
+/// Returns `true` if the GUI is using the input device that
+/// produced `event`, meaning the event shouldn't be passed on
+/// to gameplay code.
+fn gui_wants_event(egui_ctx: &egui::CtxRef, event: &Event) -> bool {
+	match event {
+		Event::KeyPressed { .. } | Event::KeyReleased { .. } | Event::TextInput { .. } => {
+			egui_ctx.wants_keyboard_input()
+		}
+		Event::MouseButtonPressed { .. }
+		| Event::MouseButtonReleased { .. }
+		| Event::MouseMoved { .. }
+		| Event::MouseWheelMoved { .. } => egui_ctx.is_using_pointer(),
+		_ => false,
+	}
+}
+
+/// Returns `true` if a GUI wants `event` for itself, meaning GUIs
+/// with a lower priority shouldn't receive it.
+///
+/// Releases and mouse movement are always shared so lower GUIs
+/// don't get stuck thinking a button is held down.
+fn gui_blocks_event(egui_ctx: &egui::CtxRef, event: &Event) -> bool {
+	match event {
+		Event::KeyPressed { .. } | Event::TextInput { .. } => egui_ctx.wants_keyboard_input(),
+		Event::MouseButtonPressed { .. } | Event::MouseWheelMoved { .. } => {
+			egui_ctx.wants_pointer_input()
+		}
+		_ => false,
+	}
+}
+
+/*
+A note about the order of events:
+
+Tetra's game loop is:
+- Poll and dispatch events
+- Run the update callback (may not happen every frame depending
+on the timestamp setting)
+- Run the draw callback
+
+We don't want to dispatch mouse and keyboard events to the
+gameplay code if egui wants them, but egui can't tell us if
+it wants them until after we call end_frame(). So we need
+to run the UI code at the beginning of the game loop (but
+not draw the result until the end of the game loop, of course).
+
+Here's the order of operations I settled on:
+- Whenever event is called, send it to the egui contexts (from highest
+to lowest priority, stopping if a context wanted it last frame) and
+queue it up for later
+- At the beginning of update, run the UI callbacks and save the
+resulting meshes and scissor rectangles. Then, dispatch queued
+events to the plugins and gameplay code (unless the UI wanted them,
+or, when using a viewport, unless they happened outside of it).
+- In the draw callback, draw gameplay first, then plugins, then UI
+(or only the UI layers above the gameplay, if some are drawn below it)
+*/
+
+impl<E: From<Error>> tetra::State<E> for StateWrapper<E> {
+	fn update(&mut self, ctx: &mut tetra::Context) -> Result<(), E> {
+		#[cfg(feature = "replay")]
+		self.replay_events(ctx)?;
+		if let Some(script) = &mut self.ui_script {
+			for event in script.next_events(ctx, &self.contexts[MAIN].egui)? {
+				self.receive_event(ctx, event)?;
+			}
+		}
+		let ui_visible = self.is_ui_visible();
+		if !ui_visible {
+			if let Some(viewport) = &mut self.viewport {
+				viewport.fill_window(ctx);
+			}
+		}
+		for context in self
+			.contexts
+			.iter_mut()
+			.filter(|context| ui_visible && context.visible)
+		{
+			context.prepare_frame(ctx)?;
+			context.egui.begin_frame(ctx)?;
+			let egui_ctx = context.egui.ctx().clone();
+			if context.name == MAIN_CONTEXT {
+				self.state.ui(ctx, &egui_ctx)?;
+			} else {
+				self.state.context_ui(ctx, &context.name, &egui_ctx)?;
+			}
+			for entry in &mut self.plugins {
+				if entry.context == context.name {
+					entry.plugin.ui(ctx, &egui_ctx)?;
+				}
+			}
+			if context.name == MAIN_CONTEXT {
+				if let Some(viewport) = &mut self.viewport {
+					viewport.show(&egui_ctx);
+				}
+			}
+			context.egui.end_frame(ctx)?;
+		}
+
+		let main_ctx = self.ctx().clone();
+		for (time, event) in std::mem::take(&mut self.events) {
+			// a held key is forgotten as soon as it's released, even if
+			// a plugin ends up consuming the release
+			let game_key_released = match &event {
+				Event::KeyReleased { key } => self.keys_down.remove(key),
+				_ => false,
+			};
+			let delivery = if ui_visible && self.gui_wants_event(&event) && !game_key_released {
+				EventDelivery::Gui
+			} else {
+				let mapped = match &self.viewport {
+					Some(viewport) => viewport.map_event(&event),
+					None => Some(event.clone()),
+				};
+				match mapped {
+					Some(mapped) => {
+						let mut delivery = EventDelivery::Game;
+						for entry in &mut self.plugins {
+							let egui_ctx = context_ctx(&self.contexts, &entry.context);
+							if entry.plugin.event(ctx, &egui_ctx, &mapped)? {
+								delivery = EventDelivery::Plugin;
+								break;
+							}
+						}
+						if delivery == EventDelivery::Game {
+							// the time controls are read for each event so pausing
+							// or stepping from a plugin takes effect right away, and
+							// releases still get through so keys held when the game
+							// was paused don't stay down after resuming
+							if !game_key_released && gameplay_stopped(&main_ctx) {
+								delivery = EventDelivery::Paused;
+							} else {
+								if let Event::KeyPressed { key } = &mapped {
+									self.keys_down.insert(*key);
+								}
+								self.state.event(ctx, &main_ctx, mapped)?;
+							}
+						}
+						delivery
+					}
+					None => EventDelivery::OutsideViewport,
+				}
+			};
+			self.inspect_event(
+				ctx,
+				EventRecord {
+					event,
+					time,
+					delivery,
+				},
+			)?;
+		}
+
+		let ticks = if UI_ENABLED {
+			TimeControls::edit(&main_ctx, TimeControls::take_ticks)
+		} else {
+			1
+		};
+		for _ in 0..ticks {
+			self.state.update(ctx, &main_ctx)?;
+		}
+		for entry in &mut self.plugins {
+			let egui_ctx = context_ctx(&self.contexts, &entry.context);
+			entry.plugin.update(ctx, &egui_ctx)?;
+		}
+		#[cfg(feature = "replay")]
+		{
+			self.frame += 1;
+		}
+		Ok(())
+	}
+
+	fn draw(&mut self, ctx: &mut tetra::Context) -> Result<(), E> {
+		let main_ctx = self.ctx().clone();
+		let ui_visible = self.is_ui_visible();
+		let game_above = match &self.viewport {
+			Some(viewport) => {
+				graphics::set_canvas(ctx, viewport.canvas());
+				None
+			}
+			None => self
+				.game_above
+				.filter(|_| ui_visible && self.contexts[MAIN].visible),
+		};
+		if let Some(order) = game_above {
+			self.contexts[MAIN].egui.draw_layers(ctx, ..=order);
+		}
+		self.state.draw(ctx, &main_ctx)?;
+		for entry in &mut self.plugins {
+			let egui_ctx = context_ctx(&self.contexts, &entry.context);
+			entry.plugin.draw(ctx, &egui_ctx)?;
+		}
+		if let Some(viewport) = &self.viewport {
+			graphics::reset_canvas(ctx);
+			graphics::clear(ctx, graphics::Color::BLACK);
+			if !ui_visible {
+				viewport.draw_to_window(ctx);
+			}
+		}
+		if !ui_visible {
+			return Ok(());
+		}
+		for &index in &self.context_order {
+			let context = &mut self.contexts[index];
+			if !context.visible {
+				continue;
+			}
+			match game_above {
+				Some(order) if index == MAIN => context
+					.egui
+					.draw_layers(ctx, (Bound::Excluded(order), Bound::Unbounded)),
+				_ => context.egui.draw_frame(ctx),
+			}
+		}
+		Ok(())
+	}
+
+	fn event(&mut self, ctx: &mut tetra::Context, event: Event) -> Result<(), E> {
+		#[cfg(feature = "replay")]
+		if self.replay.is_some() && !replay::is_window_event(&event) {
+			return Ok(());
+		}
+		self.receive_event(ctx, event)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct TestState;
+
+	impl State for TestState {}
+
+	fn ctrl_held(wrapper: &StateWrapper<Error>) -> Vec<bool> {
+		wrapper
+			.contexts
+			.iter()
+			.map(|context| context.egui.raw_input.modifiers.ctrl)
+			.collect()
+	}
+
+	#[test]
+	fn modifier_released_while_hidden() {
+		let mut wrapper = StateWrapper::new(TestState).with_context(NamedContext::new("debug"));
+		wrapper.update_modifiers(&Event::KeyPressed { key: Key::LeftCtrl });
+		assert_eq!(ctrl_held(&wrapper), [true, true]);
+		// the release happens while the GUI and one of the contexts
+		// aren't receiving input
+		wrapper.set_ui_visible(false);
+		wrapper.context_mut("debug").unwrap().set_visible(false);
+		wrapper.update_modifiers(&Event::KeyReleased { key: Key::LeftCtrl });
+		wrapper.set_ui_visible(true);
+		wrapper.context_mut("debug").unwrap().set_visible(true);
+		assert_eq!(ctrl_held(&wrapper), [false, false]);
+	}
+
+	#[test]
+	fn other_keys_leave_modifiers_alone() {
+		let mut wrapper = StateWrapper::new(TestState);
+		wrapper.update_modifiers(&Event::KeyPressed {
+			key: Key::RightCtrl,
+		});
+		wrapper.update_modifiers(&Event::KeyReleased { key: Key::C });
+		assert_eq!(ctrl_held(&wrapper), [true]);
+	}
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+/// Statistics about the work an [`EguiWrapper`](crate::EguiWrapper)
+/// did for a GUI frame.
+///
+/// The statistics for the latest finished frame can be read with
+/// [`EguiWrapper::frame_stats`](crate::EguiWrapper::frame_stats), or from
+/// GUI code with [`FrameStats::latest`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+	/// The time between the start of [`begin_frame`](crate::EguiWrapper::begin_frame)
+	/// and the start of [`end_frame`](crate::EguiWrapper::end_frame), which
+	/// includes constructing the GUI.
+	pub ui_time: Duration,
+	/// The time spent turning egui's shapes into meshes.
+	pub tessellation_time: Duration,
+	/// The time spent drawing the frame's meshes.
+	pub draw_time: Duration,
+	/// The number of meshes the frame was split into.
+	pub mesh_count: usize,
+	/// The total number of vertices in the frame's meshes.
+	pub vertex_count: usize,
+	/// The total number of indices in the frame's meshes.
+	pub index_count: usize,
+	/// The number of vertex buffers, index buffers, and textures
+	/// uploaded to the GPU for the frame.
+	pub buffer_uploads: usize,
+}
+
+impl FrameStats {
+	/// Returns the statistics for the latest finished frame of the
+	/// given egui context, or `None` if the context isn't managed by an
+	/// [`EguiWrapper`](crate::EguiWrapper) or hasn't finished a frame yet.
+	pub fn latest(egui_ctx: &egui::CtxRef) -> Option<Self> {
+		egui_ctx.memory().data.get_temp(Self::id())
+	}
+
+	pub(crate) fn id() -> egui::Id {
+		egui::Id::new("egui_tetra_frame_stats")
+	}
+}
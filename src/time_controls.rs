@@ -0,0 +1,231 @@
+/// The highest time scale gameplay can run at.
+pub const MAX_TIME_SCALE: f32 = 8.0;
+
+/// Controls for pausing, stepping, and scaling the speed of a
+/// [`StateWrapper`](crate::StateWrapper)'s gameplay.
+///
+/// These only affect the game's [`State::update`](crate::State::update)
+/// callback. [`State::ui`](crate::State::ui), [`State::draw`](crate::State::draw),
+/// plugins, and the GUI keep running while gameplay is paused, so a frozen
+/// frame can be inspected with live debug panels. Events that would be sent
+/// to the game while it's paused are dropped, unless they arrive during an
+/// update where a step runs or release a key the game saw pressed.
+///
+/// The time scale changes how many times the game updates per Tetra
+/// update, so it works best with a fixed timestep. For example, a time
+/// scale of `0.5` updates the game every other tick, and a time scale of
+/// `2.0` updates the game twice per tick.
+///
+/// The controls are stored in the main egui context's memory, so they can
+/// be read and changed from GUI code, plugins, or the game:
+///
+/// ```
+/// # fn ui(egui_ctx: &egui_tetra::egui::CtxRef) {
+/// use egui_tetra::TimeControls;
+///
+/// TimeControls::edit(egui_ctx, |controls| controls.set_time_scale(0.25));
+/// # }
+/// ```
+///
+/// [`TimeControlPanel`](crate::TimeControlPanel) shows a window with
+/// these controls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeControls {
+	paused: bool,
+	pending_steps: u32,
+	time_scale: f32,
+	/// The fraction of a tick left over from previous updates.
+	accumulator: f32,
+	ticks: u64,
+}
+
+impl TimeControls {
+	/// Returns the current time controls of the given egui context.
+	///
+	/// If the context isn't managed by a [`StateWrapper`](crate::StateWrapper),
+	/// the controls have no effect.
+	pub fn get(egui_ctx: &egui::CtxRef) -> Self {
+		egui_ctx
+			.memory()
+			.data
+			.get_temp(Self::id())
+			.unwrap_or_default()
+	}
+
+	/// Changes the time controls of the given egui context.
+	pub fn edit<R>(egui_ctx: &egui::CtxRef, edit: impl FnOnce(&mut Self) -> R) -> R {
+		let mut controls = Self::get(egui_ctx);
+		let result = edit(&mut controls);
+		egui_ctx.memory().data.insert_temp(Self::id(), controls);
+		result
+	}
+
+	/// Returns `true` if gameplay is paused.
+	pub fn is_paused(&self) -> bool {
+		self.paused
+	}
+
+	/// Pauses or resumes gameplay.
+	///
+	/// Resuming gameplay cancels any steps that haven't run yet.
+	pub fn set_paused(&mut self, paused: bool) {
+		self.paused = paused;
+		if !paused {
+			self.pending_steps = 0;
+		}
+	}
+
+	/// Pauses gameplay and queues up a number of game updates to run,
+	/// one per Tetra update.
+	pub fn step(&mut self, ticks: u32) {
+		self.paused = true;
+		self.pending_steps = self.pending_steps.saturating_add(ticks);
+	}
+
+	/// Returns the number of queued steps that haven't run yet.
+	pub fn pending_steps(&self) -> u32 {
+		self.pending_steps
+	}
+
+	/// Returns how fast gameplay runs compared to normal.
+	pub fn time_scale(&self) -> f32 {
+		self.time_scale
+	}
+
+	/// Sets how fast gameplay runs compared to normal.
+	///
+	/// The time scale is clamped between `0.0` and [`MAX_TIME_SCALE`].
+	pub fn set_time_scale(&mut self, time_scale: f32) {
+		self.time_scale = time_scale.clamp(0.0, MAX_TIME_SCALE);
+	}
+
+	/// Returns the number of times the game has updated.
+	pub fn ticks(&self) -> u64 {
+		self.ticks
+	}
+
+	/// Returns `true` if gameplay is paused with no steps left to run,
+	/// so the game won't update during the current Tetra update.
+	pub(crate) fn is_stopped(&self) -> bool {
+		self.paused && self.pending_steps == 0
+	}
+
+	/// Returns how many times the game should update during the current
+	/// Tetra update.
+	pub(crate) fn take_ticks(&mut self) -> u32 {
+		let ticks = if self.paused {
+			if self.pending_steps == 0 {
+				return 0;
+			}
+			self.pending_steps -= 1;
+			1
+		} else {
+			self.accumulator += self.time_scale;
+			let ticks = self.accumulator.floor();
+			self.accumulator -= ticks;
+			ticks as u32
+		};
+		self.ticks += ticks as u64;
+		ticks
+	}
+
+	pub(crate) fn id() -> egui::Id {
+		egui::Id::new("egui_tetra_time_controls")
+	}
+}
+
+impl Default for TimeControls {
+	fn default() -> Self {
+		Self {
+			paused: false,
+			pending_steps: 0,
+			time_scale: 1.0,
+			accumulator: 0.0,
+			ticks: 0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Returns the number of ticks run in each of `updates` updates.
+	fn take(controls: &mut TimeControls, updates: usize) -> Vec<u32> {
+		(0..updates).map(|_| controls.take_ticks()).collect()
+	}
+
+	#[test]
+	fn normal_speed() {
+		let mut controls = TimeControls::default();
+		assert_eq!(take(&mut controls, 3), vec![1, 1, 1]);
+		assert_eq!(controls.ticks(), 3);
+	}
+
+	#[test]
+	fn fractional_scale() {
+		let mut controls = TimeControls::default();
+		controls.set_time_scale(0.5);
+		assert_eq!(take(&mut controls, 4), vec![0, 1, 0, 1]);
+		controls.set_time_scale(0.25);
+		assert_eq!(take(&mut controls, 4), vec![0, 0, 0, 1]);
+		controls.set_time_scale(1.5);
+		assert_eq!(take(&mut controls, 4), vec![1, 2, 1, 2]);
+		assert_eq!(controls.ticks(), 2 + 1 + 6);
+	}
+
+	#[test]
+	fn paused() {
+		let mut controls = TimeControls::default();
+		controls.set_paused(true);
+		assert_eq!(take(&mut controls, 3), vec![0, 0, 0]);
+		assert_eq!(controls.ticks(), 0);
+	}
+
+	#[test]
+	fn stepping_while_paused() {
+		let mut controls = TimeControls::default();
+		controls.set_time_scale(4.0);
+		controls.step(2);
+		assert!(controls.is_paused());
+		assert_eq!(controls.pending_steps(), 2);
+		// steps run one tick per update, regardless of the time scale
+		assert_eq!(take(&mut controls, 3), vec![1, 1, 0]);
+		assert_eq!(controls.pending_steps(), 0);
+		assert!(controls.is_paused());
+		assert_eq!(controls.ticks(), 2);
+	}
+
+	#[test]
+	fn stopped_until_stepped() {
+		let mut controls = TimeControls::default();
+		assert!(!controls.is_stopped());
+		controls.set_paused(true);
+		assert!(controls.is_stopped());
+		controls.step(1);
+		assert!(!controls.is_stopped());
+		controls.take_ticks();
+		assert!(controls.is_stopped());
+	}
+
+	#[test]
+	fn resuming_cancels_steps() {
+		let mut controls = TimeControls::default();
+		controls.step(5);
+		controls.take_ticks();
+		controls.set_paused(false);
+		assert_eq!(controls.pending_steps(), 0);
+		assert_eq!(take(&mut controls, 2), vec![1, 1]);
+	}
+
+	#[test]
+	fn time_scale_is_clamped() {
+		let mut controls = TimeControls::default();
+		controls.set_time_scale(100.0);
+		assert_eq!(controls.time_scale(), MAX_TIME_SCALE);
+		assert_eq!(take(&mut controls, 1), vec![MAX_TIME_SCALE as u32]);
+		controls.set_time_scale(-1.0);
+		assert_eq!(controls.time_scale(), 0.0);
+		assert_eq!(take(&mut controls, 3), vec![0, 0, 0]);
+	}
+}
@@ -0,0 +1,190 @@
+use tetra::{
+	graphics::{Canvas, DrawParams},
+	math::Vec2,
+	Event,
+};
+
+use crate::{
+	convert::{FromEgui, FromTetra},
+	EguiWrapper,
+};
+
+/// Displays a [`Canvas`] the game is drawn to as an image in the GUI
+/// and remaps input events over that image into game coordinates.
+///
+/// This is useful for editors, where the game should be docked among
+/// tool panels rather than filling the window. [`StateWrapper`](crate::StateWrapper)
+/// can manage a viewport for you (see
+/// [`StateWrapper::with_viewport`](crate::StateWrapper::with_viewport)).
+///
+/// Mouse input is sent to the game while the pointer is over the image,
+/// and keyboard input is sent to the game after the image is clicked.
+/// Since Tetra's mouse position functions still report window
+/// coordinates, games running in a viewport should use the positions
+/// from [`MouseMoved`](Event::MouseMoved) events or
+/// [`to_game_position`](Viewport::to_game_position) instead.
+pub struct Viewport {
+	canvas: Canvas,
+	texture_id: egui::TextureId,
+	image_rect: Option<egui::Rect>,
+	hovered: bool,
+	dragged: bool,
+	focused: bool,
+}
+
+impl Viewport {
+	/// Creates a new [`Viewport`] that displays the given canvas,
+	/// registering the canvas's texture with the [`EguiWrapper`].
+	pub fn new(egui: &mut EguiWrapper, canvas: Canvas) -> Self {
+		let texture_id = egui.register_texture(canvas.texture().clone());
+		Self {
+			canvas,
+			texture_id,
+			image_rect: None,
+			hovered: false,
+			dragged: false,
+			focused: false,
+		}
+	}
+
+	/// Registers the canvas's texture with another [`EguiWrapper`],
+	/// for when the context the viewport is shown in is replaced.
+	pub(crate) fn register_texture(&mut self, egui: &mut EguiWrapper) {
+		self.texture_id = egui.register_texture(self.canvas.texture().clone());
+	}
+
+	/// Returns the canvas the game should be drawn to.
+	pub fn canvas(&self) -> &Canvas {
+		&self.canvas
+	}
+
+	/// Returns the ID of the canvas's texture in the egui context.
+	pub fn texture_id(&self) -> egui::TextureId {
+		self.texture_id
+	}
+
+	/// Shows the game image in a [`CentralPanel`](egui::CentralPanel),
+	/// filling the space left over by other panels.
+	///
+	/// This should be called after any other panels are shown.
+	pub fn show(&mut self, egui_ctx: &egui::CtxRef) -> egui::Response {
+		egui::CentralPanel::default()
+			.show(egui_ctx, |ui| self.ui(ui))
+			.inner
+	}
+
+	/// Shows the game image in a [`Ui`](egui::Ui), scaled to fit the
+	/// available space while keeping its aspect ratio.
+	pub fn ui(&mut self, ui: &mut egui::Ui) -> egui::Response {
+		let (rect, response) =
+			ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+		let canvas_size = egui::vec2(self.canvas.width() as f32, self.canvas.height() as f32);
+		let scale = (rect.width() / canvas_size.x).min(rect.height() / canvas_size.y);
+		let image_rect = egui::Rect::from_center_size(rect.center(), canvas_size * scale);
+		egui::Image::new(self.texture_id, image_rect.size()).paint_at(ui, image_rect);
+		self.image_rect = Some(image_rect);
+		self.hovered = response
+			.hover_pos()
+			.is_some_and(|pos| image_rect.contains(pos));
+		self.dragged = response.dragged() || response.drag_released();
+		// egui's keyboard focus is dropped whenever escape is pressed,
+		// which games commonly use, so we keep track of focus ourselves
+		if response.clicked() || response.drag_started() {
+			self.focused = true;
+		} else if response.clicked_elsewhere() {
+			self.focused = false;
+		}
+		response
+	}
+
+	/// Fits the game image to the whole window, for when the GUI
+	/// is hidden. All input is sent to the game while this is the case.
+	pub(crate) fn fill_window(&mut self, ctx: &tetra::Context) {
+		let window_size = egui::vec2(
+			tetra::window::get_width(ctx) as f32,
+			tetra::window::get_height(ctx) as f32,
+		);
+		let canvas_size = egui::vec2(self.canvas.width() as f32, self.canvas.height() as f32);
+		let scale = (window_size.x / canvas_size.x).min(window_size.y / canvas_size.y);
+		self.image_rect = Some(egui::Rect::from_center_size(
+			(window_size / 2.0).to_pos2(),
+			canvas_size * scale,
+		));
+		self.hovered = true;
+		self.dragged = false;
+		self.focused = true;
+	}
+
+	/// Draws the game image directly to the window at the position
+	/// set by [`fill_window`](Self::fill_window).
+	pub(crate) fn draw_to_window(&self, ctx: &mut tetra::Context) {
+		if let (Some(image_rect), Some(scale)) = (self.image_rect, self.scale()) {
+			self.canvas.draw(
+				ctx,
+				DrawParams::new()
+					.position(Vec2::from_egui(image_rect.min))
+					.scale(Vec2::broadcast(scale)),
+			);
+		}
+	}
+
+	/// Converts a position in window coordinates to a position in
+	/// game coordinates, or returns `None` if the image hasn't been
+	/// shown yet.
+	pub fn to_game_position(&self, window_position: Vec2<f32>) -> Option<Vec2<f32>> {
+		let image_rect = self.image_rect?;
+		let normalized =
+			(egui::Pos2::from_tetra(window_position) - image_rect.min) / image_rect.size();
+		Some(Vec2::from_egui(egui::pos2(
+			normalized.x * self.canvas.width() as f32,
+			normalized.y * self.canvas.height() as f32,
+		)))
+	}
+
+	/// Converts a position in game coordinates to a position in
+	/// window coordinates, or returns `None` if the image hasn't been
+	/// shown yet.
+	pub fn to_window_position(&self, game_position: Vec2<f32>) -> Option<Vec2<f32>> {
+		let image_rect = self.image_rect?;
+		let scale = self.scale()?;
+		Some(Vec2::from_egui(
+			image_rect.min + egui::vec2(game_position.x, game_position.y) * scale,
+		))
+	}
+
+	/// Returns the number of window pixels per game pixel, or `None`
+	/// if the image hasn't been shown yet.
+	pub fn scale(&self) -> Option<f32> {
+		let image_rect = self.image_rect?;
+		Some(image_rect.width() / self.canvas.width() as f32)
+	}
+
+	/// Returns the event as it should be seen by the game, or `None` if
+	/// the event shouldn't be sent to the game.
+	///
+	/// Mouse positions are converted to game coordinates. Events that
+	/// aren't mouse or keyboard input are always passed through.
+	pub fn map_event(&self, event: &Event) -> Option<Event> {
+		match event {
+			Event::MouseMoved { position, delta } => {
+				if !(self.hovered || self.dragged) {
+					return None;
+				}
+				Some(Event::MouseMoved {
+					position: self.to_game_position(*position)?,
+					delta: *delta / self.scale()?,
+				})
+			}
+			Event::MouseButtonPressed { .. } | Event::MouseWheelMoved { .. } => {
+				self.hovered.then(|| event.clone())
+			}
+			Event::MouseButtonReleased { .. } => {
+				(self.hovered || self.dragged).then(|| event.clone())
+			}
+			Event::KeyPressed { .. } | Event::KeyReleased { .. } | Event::TextInput { .. } => {
+				self.focused.then(|| event.clone())
+			}
+			_ => Some(event.clone()),
+		}
+	}
+}
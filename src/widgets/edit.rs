@@ -0,0 +1,357 @@
+use egui::{
+	color::{gamma_from_linear, linear_from_gamma, Hsva},
+	widgets::color_picker::{self, Alpha},
+};
+use tetra::{
+	graphics::{Color, DrawParams, Rectangle},
+	math::Vec2,
+};
+
+/// The size of the 2D pad shown by [`Vec2Edit`], in points.
+const PAD_SIZE: f32 = 64.0;
+
+/// Converts a Tetra color, whose components are sRGB-encoded, to an
+/// egui [`Hsva`], whose components are linear.
+fn tetra_color_to_hsva(color: Color) -> Hsva {
+	Hsva::from_rgba_unmultiplied(
+		linear_from_gamma(color.r),
+		linear_from_gamma(color.g),
+		linear_from_gamma(color.b),
+		color.a,
+	)
+}
+
+/// Converts an egui [`Hsva`] to a Tetra color.
+fn hsva_to_tetra_color(hsva: Hsva) -> Color {
+	let [r, g, b, a] = hsva.to_rgba_unmultiplied();
+	Color::rgba(
+		gamma_from_linear(r),
+		gamma_from_linear(g),
+		gamma_from_linear(b),
+		a,
+	)
+}
+
+/// A widget for editing a [`Vec2<f32>`] with a pair of drag values,
+/// and optionally a 2D pad that sets both components at once.
+///
+/// ```
+/// # fn ui(ui: &mut egui_tetra::egui::Ui, velocity: &mut tetra::math::Vec2<f32>) {
+/// use egui_tetra::Vec2Edit;
+///
+/// ui.add(Vec2Edit::new(velocity).pad(100.0));
+/// # }
+/// ```
+pub struct Vec2Edit<'a> {
+	value: &'a mut Vec2<f32>,
+	speed: f32,
+	pad_extent: Option<f32>,
+}
+
+impl<'a> Vec2Edit<'a> {
+	/// Creates a new [`Vec2Edit`] for the given vector.
+	pub fn new(value: &'a mut Vec2<f32>) -> Self {
+		Self {
+			value,
+			speed: 1.0,
+			pad_extent: None,
+		}
+	}
+
+	/// Sets how much the components change per point the mouse is
+	/// dragged and returns the widget.
+	///
+	/// Defaults to `1.0`.
+	pub fn speed(mut self, speed: f32) -> Self {
+		self.speed = speed;
+		self
+	}
+
+	/// Shows a 2D pad next to the drag values and returns the widget.
+	///
+	/// Clicking or dragging on the pad sets the vector to a value between
+	/// `-extent` and `extent` on both axes.
+	pub fn pad(mut self, extent: f32) -> Self {
+		self.pad_extent = Some(extent);
+		self
+	}
+}
+
+impl egui::Widget for Vec2Edit<'_> {
+	fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+		let Self {
+			value,
+			speed,
+			pad_extent,
+		} = self;
+		let egui::InnerResponse {
+			inner: changed,
+			mut response,
+		} = ui.horizontal(|ui| {
+			let mut changed = ui
+				.add(
+					egui::DragValue::new(&mut value.x)
+						.speed(speed)
+						.prefix("x: "),
+				)
+				.changed();
+			changed |= ui
+				.add(
+					egui::DragValue::new(&mut value.y)
+						.speed(speed)
+						.prefix("y: "),
+				)
+				.changed();
+			if let Some(extent) = pad_extent.filter(|extent| *extent > 0.0) {
+				let (rect, response) = ui.allocate_exact_size(
+					egui::Vec2::splat(PAD_SIZE),
+					egui::Sense::click_and_drag(),
+				);
+				if let Some(pointer_pos) = response.interact_pointer_pos() {
+					if response.clicked() || response.dragged() {
+						let normalized = ((pointer_pos - rect.center()) / (rect.size() / 2.0))
+							.clamp(egui::Vec2::splat(-1.0), egui::Vec2::splat(1.0));
+						*value = Vec2::new(normalized.x, normalized.y) * extent;
+						changed = true;
+					}
+				}
+				let visuals = ui.style().interact(&response);
+				let painter = ui.painter_at(rect);
+				painter.rect(
+					rect,
+					visuals.corner_radius,
+					ui.visuals().extreme_bg_color,
+					visuals.bg_stroke,
+				);
+				painter.line_segment(
+					[rect.center_top(), rect.center_bottom()],
+					ui.visuals().widgets.noninteractive.bg_stroke,
+				);
+				painter.line_segment(
+					[rect.left_center(), rect.right_center()],
+					ui.visuals().widgets.noninteractive.bg_stroke,
+				);
+				let marker = rect.center()
+					+ egui::vec2(
+						(value.x / extent).clamp(-1.0, 1.0),
+						(value.y / extent).clamp(-1.0, 1.0),
+					) * (rect.size() / 2.0);
+				painter.circle_filled(marker, 3.0, visuals.fg_stroke.color);
+			}
+			changed
+		});
+		if changed {
+			response.mark_changed();
+		}
+		response
+	}
+}
+
+/// A widget for editing a Tetra [`Color`] with egui's color picker.
+///
+/// Tetra's color components are sRGB-encoded, while egui's color picker
+/// works with linear colors, so components are converted in both
+/// directions to keep the picked color and the drawn color the same.
+///
+/// The ID source has to be unique among the widgets in the same
+/// [`Ui`](egui::Ui).
+pub struct ColorEdit<'a> {
+	id_source: egui::Id,
+	color: &'a mut Color,
+	alpha: bool,
+}
+
+impl<'a> ColorEdit<'a> {
+	/// Creates a new [`ColorEdit`] for the given color.
+	pub fn new(id_source: impl std::hash::Hash, color: &'a mut Color) -> Self {
+		Self {
+			id_source: egui::Id::new(id_source),
+			color,
+			alpha: true,
+		}
+	}
+
+	/// Sets whether the alpha component can be edited and returns
+	/// the widget.
+	///
+	/// Defaults to `true`.
+	pub fn alpha(mut self, alpha: bool) -> Self {
+		self.alpha = alpha;
+		self
+	}
+}
+
+impl egui::Widget for ColorEdit<'_> {
+	fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+		// converting to HSVA and back loses the hue of grays, so we remember
+		// the HSVA value as long as the color hasn't changed elsewhere
+		let cache_id = ui.make_persistent_id(self.id_source);
+		let cached = ui
+			.memory()
+			.data
+			.get_temp::<(Color, Hsva)>(cache_id)
+			.filter(|(color, _)| color == self.color);
+		let mut hsva = match cached {
+			Some((_, hsva)) => hsva,
+			None => tetra_color_to_hsva(*self.color),
+		};
+		let alpha = if self.alpha {
+			Alpha::OnlyBlend
+		} else {
+			Alpha::Opaque
+		};
+		let response = color_picker::color_edit_button_hsva(ui, &mut hsva, alpha);
+		if response.changed() {
+			*self.color = hsva_to_tetra_color(hsva);
+		}
+		ui.memory().data.insert_temp(cache_id, (*self.color, hsva));
+		response
+	}
+}
+
+/// A widget for editing a [`Rectangle<f32>`]'s position and size.
+pub struct RectangleEdit<'a> {
+	rectangle: &'a mut Rectangle,
+	speed: f32,
+}
+
+impl<'a> RectangleEdit<'a> {
+	/// Creates a new [`RectangleEdit`] for the given rectangle.
+	pub fn new(rectangle: &'a mut Rectangle) -> Self {
+		Self {
+			rectangle,
+			speed: 1.0,
+		}
+	}
+
+	/// Sets how much the values change per point the mouse is dragged
+	/// and returns the widget.
+	///
+	/// Defaults to `1.0`.
+	pub fn speed(mut self, speed: f32) -> Self {
+		self.speed = speed;
+		self
+	}
+}
+
+impl egui::Widget for RectangleEdit<'_> {
+	fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+		let Self { rectangle, speed } = self;
+		let egui::InnerResponse {
+			inner: changed,
+			mut response,
+		} = ui.vertical(|ui| {
+			let mut changed = false;
+			ui.horizontal(|ui| {
+				changed |= ui
+					.add(
+						egui::DragValue::new(&mut rectangle.x)
+							.speed(speed)
+							.prefix("x: "),
+					)
+					.changed();
+				changed |= ui
+					.add(
+						egui::DragValue::new(&mut rectangle.y)
+							.speed(speed)
+							.prefix("y: "),
+					)
+					.changed();
+			});
+			ui.horizontal(|ui| {
+				changed |= ui
+					.add(
+						egui::DragValue::new(&mut rectangle.width)
+							.speed(speed)
+							.clamp_range(0.0..=f32::MAX)
+							.prefix("w: "),
+					)
+					.changed();
+				changed |= ui
+					.add(
+						egui::DragValue::new(&mut rectangle.height)
+							.speed(speed)
+							.clamp_range(0.0..=f32::MAX)
+							.prefix("h: "),
+					)
+					.changed();
+			});
+			changed
+		});
+		if changed {
+			response.mark_changed();
+		}
+		response
+	}
+}
+
+/// A widget for editing the position, scale, origin, rotation, and
+/// color of Tetra [`DrawParams`].
+///
+/// The rotation is shown in degrees.
+pub struct DrawParamsEdit<'a> {
+	params: &'a mut DrawParams,
+	id_source: egui::Id,
+}
+
+impl<'a> DrawParamsEdit<'a> {
+	/// Creates a new [`DrawParamsEdit`] for the given draw parameters.
+	pub fn new(params: &'a mut DrawParams) -> Self {
+		Self {
+			params,
+			id_source: egui::Id::new("egui_tetra_draw_params_edit"),
+		}
+	}
+
+	/// Sets a source for the widget's ID and returns the widget.
+	///
+	/// This is needed when showing more than one [`DrawParamsEdit`]
+	/// in the same [`Ui`](egui::Ui).
+	pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+		self.id_source = egui::Id::new(id_source);
+		self
+	}
+}
+
+impl egui::Widget for DrawParamsEdit<'_> {
+	fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+		let Self { params, id_source } = self;
+		let egui::InnerResponse {
+			inner: changed,
+			mut response,
+		} = egui::Grid::new(id_source).num_columns(2).show(ui, |ui| {
+			let mut changed = false;
+			ui.label("Position");
+			changed |= ui.add(Vec2Edit::new(&mut params.position)).changed();
+			ui.end_row();
+			ui.label("Scale");
+			changed |= ui
+				.add(Vec2Edit::new(&mut params.scale).speed(0.01))
+				.changed();
+			ui.end_row();
+			ui.label("Origin");
+			changed |= ui.add(Vec2Edit::new(&mut params.origin)).changed();
+			ui.end_row();
+			ui.label("Rotation");
+			let mut degrees = params.rotation.to_degrees();
+			if ui
+				.add(egui::DragValue::new(&mut degrees).speed(1.0).suffix("°"))
+				.changed()
+			{
+				params.rotation = degrees.to_radians();
+				changed = true;
+			}
+			ui.end_row();
+			ui.label("Color");
+			changed |= ui
+				.add(ColorEdit::new(id_source.with("color"), &mut params.color))
+				.changed();
+			ui.end_row();
+			changed
+		});
+		if changed {
+			response.mark_changed();
+		}
+		response
+	}
+}
@@ -0,0 +1,393 @@
+use std::hash::Hash;
+
+use tetra::math::{Mat4, Vec2};
+
+use crate::{
+	convert::{FromEgui, FromTetra},
+	screen_to_world, world_to_screen,
+};
+
+/// How close the pointer has to be to a handle to grab it, in points.
+const GRAB_DISTANCE: f32 = 8.0;
+/// Half the size of the square handles, in points.
+const HANDLE_RADIUS: f32 = 5.0;
+const LINE_WIDTH: f32 = 2.0;
+
+const X_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 70, 70);
+const Y_COLOR: egui::Color32 = egui::Color32::from_rgb(90, 200, 90);
+const ROTATE_COLOR: egui::Color32 = egui::Color32::from_rgb(80, 140, 240);
+const CENTER_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 230, 230);
+const HIGHLIGHT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 220, 60);
+
+/// The position, rotation, and scale of an object edited by a
+/// [`TransformGizmo`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoTransform {
+	/// The position of the object in world coordinates.
+	pub position: Vec2<f32>,
+	/// The rotation of the object in radians.
+	pub rotation: f32,
+	/// The scale of the object.
+	pub scale: Vec2<f32>,
+}
+
+impl GizmoTransform {
+	/// Creates a new [`GizmoTransform`] at the given position with no
+	/// rotation and a scale of `1.0`.
+	pub fn new(position: Vec2<f32>) -> Self {
+		Self {
+			position,
+			rotation: 0.0,
+			scale: Vec2::one(),
+		}
+	}
+}
+
+impl Default for GizmoTransform {
+	fn default() -> Self {
+		Self::new(Vec2::zero())
+	}
+}
+
+/// Which handles a [`TransformGizmo`] shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoMode {
+	/// Arrows for moving the object along the world's X and Y axes,
+	/// and a center handle for moving it freely.
+	Translate,
+	/// A ring for rotating the object around its position.
+	Rotate,
+	/// Handles for scaling the object along its own X and Y axes,
+	/// and a center handle for scaling it uniformly.
+	Scale,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Handle {
+	X,
+	Y,
+	Center,
+	Ring,
+}
+
+/// The state of a drag, stored in egui's memory between frames.
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+	handle: Handle,
+	start_transform: GizmoTransform,
+	start_pointer: Vec2<f32>,
+}
+
+/// The screen-space layout of a gizmo's handles.
+struct Layout {
+	center: egui::Pos2,
+	x_end: egui::Pos2,
+	y_end: egui::Pos2,
+	rotation_end: egui::Pos2,
+	radius: f32,
+}
+
+/// Translate, rotate, and scale handles for editing an object's
+/// [`GizmoTransform`] in the game world, painted in an
+/// [`egui::Area`] above the game.
+///
+/// Like [`WorldArea`](crate::WorldArea), the gizmo is positioned using a
+/// transform matrix (usually from a [`Camera`](tetra::graphics::Camera)),
+/// while the handles stay the same size on screen. Dragging a handle
+/// uses egui's pointer, so a [`StateWrapper`](crate::StateWrapper) won't
+/// pass those drags on to the game.
+///
+/// ```
+/// # fn ui(
+/// # 	egui_ctx: &egui_tetra::egui::CtxRef,
+/// # 	camera: &tetra::graphics::Camera,
+/// # 	transform: &mut egui_tetra::GizmoTransform,
+/// # ) {
+/// use egui_tetra::{GizmoMode, TransformGizmo};
+///
+/// *transform = TransformGizmo::new("selected_object")
+/// 	.mode(GizmoMode::Rotate)
+/// 	.show(egui_ctx, camera.as_matrix(), *transform)
+/// 	.inner;
+/// # }
+/// ```
+pub struct TransformGizmo {
+	id: egui::Id,
+	mode: GizmoMode,
+	size: f32,
+	order: egui::Order,
+}
+
+impl TransformGizmo {
+	/// Creates a new [`TransformGizmo`] in translate mode.
+	pub fn new(id_source: impl Hash) -> Self {
+		Self {
+			id: egui::Id::new(id_source),
+			mode: GizmoMode::Translate,
+			size: 80.0,
+			order: egui::Order::Background,
+		}
+	}
+
+	/// Sets which handles are shown and returns the gizmo.
+	///
+	/// Defaults to [`GizmoMode::Translate`].
+	pub fn mode(mut self, mode: GizmoMode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	/// Sets the length of the handles in points and returns the gizmo.
+	///
+	/// Defaults to `80.0`.
+	pub fn size(mut self, size: f32) -> Self {
+		self.size = size;
+		self
+	}
+
+	/// Sets the layer [`Order`](egui::Order) of the gizmo and returns
+	/// the gizmo.
+	///
+	/// Defaults to [`Order::Background`](egui::Order::Background),
+	/// so the gizmo is drawn behind windows.
+	pub fn order(mut self, order: egui::Order) -> Self {
+		self.order = order;
+		self
+	}
+
+	/// Shows the gizmo for an object and returns the object's edited
+	/// transform.
+	///
+	/// The returned response is marked as
+	/// [changed](egui::Response::changed) if the transform was edited.
+	pub fn show(
+		self,
+		egui_ctx: &egui::CtxRef,
+		matrix: Mat4<f32>,
+		transform: GizmoTransform,
+	) -> egui::InnerResponse<GizmoTransform> {
+		let layout = self.layout(matrix, transform);
+		let margin = GRAB_DISTANCE + HANDLE_RADIUS;
+		let rect = egui::Rect::from_center_size(
+			layout.center,
+			egui::Vec2::splat((layout.radius + margin) * 2.0),
+		);
+		let drag_id = self.id.with("egui_tetra_gizmo_drag");
+		let pointer = egui_ctx.input().pointer.interact_pos();
+		let mut drag = egui_ctx.memory().data.get_temp::<Drag>(drag_id);
+		let hovered_handle = match drag {
+			Some(drag) => Some(drag.handle),
+			None => pointer.and_then(|pointer| self.handle_at(&layout, pointer)),
+		};
+		let area = egui::Area::new(self.id)
+			.order(self.order)
+			.fixed_pos(rect.min)
+			.show(egui_ctx, |ui| {
+				ui.allocate_rect(rect, egui::Sense::hover());
+				// only sense drags over a handle, so drags that start
+				// elsewhere in the gizmo's area still reach the game
+				let sense = if hovered_handle.is_some() {
+					egui::Sense::drag()
+				} else {
+					egui::Sense::hover()
+				};
+				let response = ui.interact(rect, self.id.with("handles"), sense);
+				self.paint(ui.painter(), &layout, hovered_handle);
+				response
+			});
+		let mut response = area.inner;
+		let mut new_transform = transform;
+		if response.drag_started() {
+			if let (Some(handle), Some(pointer)) = (hovered_handle, pointer) {
+				drag = Some(Drag {
+					handle,
+					start_transform: transform,
+					start_pointer: screen_to_world(matrix, Vec2::from_egui(pointer)),
+				});
+			}
+		}
+		if response.dragged() {
+			if let (Some(drag), Some(pointer)) = (drag, pointer) {
+				let pointer = screen_to_world(matrix, Vec2::from_egui(pointer));
+				new_transform = self.drag(drag, pointer);
+			}
+		}
+		match drag {
+			Some(drag) if response.dragged() => egui_ctx.memory().data.insert_temp(drag_id, drag),
+			_ => egui_ctx.memory().data.remove::<Drag>(drag_id),
+		}
+		if new_transform != transform {
+			response.mark_changed();
+		}
+		egui::InnerResponse::new(new_transform, response)
+	}
+
+	fn layout(&self, matrix: Mat4<f32>, transform: GizmoTransform) -> Layout {
+		let to_screen =
+			|position: Vec2<f32>| egui::Pos2::from_tetra(world_to_screen(matrix, position));
+		let center = to_screen(transform.position);
+		let (x_axis, y_axis) = match self.mode {
+			GizmoMode::Translate | GizmoMode::Rotate => (Vec2::unit_x(), Vec2::unit_y()),
+			GizmoMode::Scale => local_axes(transform.rotation),
+		};
+		let (rotation_axis, _) = local_axes(transform.rotation);
+		let direction = |axis: Vec2<f32>, fallback: egui::Vec2| {
+			let direction = to_screen(transform.position + axis) - center;
+			if direction.length() > f32::EPSILON {
+				direction.normalized()
+			} else {
+				fallback
+			}
+		};
+		let x_direction = direction(x_axis, egui::Vec2::X);
+		let y_direction = direction(y_axis, egui::Vec2::Y);
+		let rotation_direction = direction(rotation_axis, egui::Vec2::X);
+		Layout {
+			center,
+			x_end: center + x_direction * self.size,
+			y_end: center + y_direction * self.size,
+			rotation_end: center + rotation_direction * self.size,
+			radius: self.size,
+		}
+	}
+
+	/// Returns the handle under a position in screen coordinates, if
+	/// there is one.
+	fn handle_at(&self, layout: &Layout, position: egui::Pos2) -> Option<Handle> {
+		match self.mode {
+			GizmoMode::Translate | GizmoMode::Scale => {
+				if layout.center.distance(position) <= GRAB_DISTANCE {
+					return Some(Handle::Center);
+				}
+				let x_distance = distance_to_segment(position, layout.center, layout.x_end);
+				let y_distance = distance_to_segment(position, layout.center, layout.y_end);
+				if x_distance.min(y_distance) > GRAB_DISTANCE {
+					None
+				} else if x_distance <= y_distance {
+					Some(Handle::X)
+				} else {
+					Some(Handle::Y)
+				}
+			}
+			GizmoMode::Rotate => {
+				let distance = (layout.center.distance(position) - layout.radius).abs();
+				if distance <= GRAB_DISTANCE {
+					Some(Handle::Ring)
+				} else {
+					None
+				}
+			}
+		}
+	}
+
+	/// Returns the transform after dragging a handle to a position in
+	/// world coordinates.
+	fn drag(&self, drag: Drag, pointer: Vec2<f32>) -> GizmoTransform {
+		let start = drag.start_transform;
+		let mut transform = start;
+		match (self.mode, drag.handle) {
+			(GizmoMode::Translate, handle) => {
+				let delta = pointer - drag.start_pointer;
+				transform.position += match handle {
+					Handle::X => Vec2::new(delta.x, 0.0),
+					Handle::Y => Vec2::new(0.0, delta.y),
+					_ => delta,
+				};
+			}
+			(GizmoMode::Rotate, _) => {
+				let start_offset = drag.start_pointer - start.position;
+				let offset = pointer - start.position;
+				let angle = offset.y.atan2(offset.x) - start_offset.y.atan2(start_offset.x);
+				transform.rotation = start.rotation + angle;
+			}
+			(GizmoMode::Scale, handle) => {
+				let (x_axis, y_axis) = local_axes(start.rotation);
+				let start_offset = drag.start_pointer - start.position;
+				let offset = pointer - start.position;
+				let factor = |axis: Vec2<f32>| {
+					let start_distance = start_offset.dot(axis);
+					if start_distance.abs() > f32::EPSILON {
+						offset.dot(axis) / start_distance
+					} else {
+						1.0
+					}
+				};
+				match handle {
+					Handle::X => transform.scale.x *= factor(x_axis),
+					Handle::Y => transform.scale.y *= factor(y_axis),
+					_ => {
+						let start_distance = start_offset.magnitude();
+						if start_distance > f32::EPSILON {
+							transform.scale *= offset.magnitude() / start_distance;
+						}
+					}
+				}
+			}
+		}
+		transform
+	}
+
+	fn paint(&self, painter: &egui::Painter, layout: &Layout, hovered_handle: Option<Handle>) {
+		let color = |handle: Handle, color: egui::Color32| {
+			if hovered_handle == Some(handle) {
+				HIGHLIGHT_COLOR
+			} else {
+				color
+			}
+		};
+		match self.mode {
+			GizmoMode::Translate | GizmoMode::Scale => {
+				for (handle, end, base_color) in [
+					(Handle::X, layout.x_end, X_COLOR),
+					(Handle::Y, layout.y_end, Y_COLOR),
+				] {
+					let stroke = egui::Stroke::new(LINE_WIDTH, color(handle, base_color));
+					if self.mode == GizmoMode::Translate {
+						painter.arrow(layout.center, end - layout.center, stroke);
+					} else {
+						painter.line_segment([layout.center, end], stroke);
+						painter.rect_filled(
+							egui::Rect::from_center_size(
+								end,
+								egui::Vec2::splat(HANDLE_RADIUS * 2.0),
+							),
+							0.0,
+							stroke.color,
+						);
+					}
+				}
+				painter.rect_filled(
+					egui::Rect::from_center_size(
+						layout.center,
+						egui::Vec2::splat(HANDLE_RADIUS * 2.0),
+					),
+					0.0,
+					color(Handle::Center, CENTER_COLOR),
+				);
+			}
+			GizmoMode::Rotate => {
+				let stroke = egui::Stroke::new(LINE_WIDTH, color(Handle::Ring, ROTATE_COLOR));
+				painter.circle_stroke(layout.center, layout.radius, stroke);
+				painter.line_segment([layout.center, layout.rotation_end], stroke);
+				painter.circle_filled(layout.center, HANDLE_RADIUS, CENTER_COLOR);
+			}
+		}
+	}
+}
+
+/// Returns an object's X and Y axes in world coordinates.
+fn local_axes(rotation: f32) -> (Vec2<f32>, Vec2<f32>) {
+	let (sin, cos) = rotation.sin_cos();
+	(Vec2::new(cos, sin), Vec2::new(-sin, cos))
+}
+
+fn distance_to_segment(position: egui::Pos2, start: egui::Pos2, end: egui::Pos2) -> f32 {
+	let segment = end - start;
+	let length_squared = segment.length_sq();
+	if length_squared <= f32::EPSILON {
+		return position.distance(start);
+	}
+	let offset = position - start;
+	let t = ((offset.x * segment.x + offset.y * segment.y) / length_squared).clamp(0.0, 1.0);
+	position.distance(start + segment * t)
+}
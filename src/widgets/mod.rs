@@ -0,0 +1,9 @@
+mod edit;
+mod gizmo;
+mod sprite_sheet;
+mod texture_viewer;
+
+pub use edit::{ColorEdit, DrawParamsEdit, RectangleEdit, Vec2Edit};
+pub use gizmo::{GizmoMode, GizmoTransform, TransformGizmo};
+pub use sprite_sheet::SpriteSheetSlicer;
+pub use texture_viewer::TextureViewer;
@@ -0,0 +1,359 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use tetra::graphics::{animation::Animation, Rectangle, Texture};
+
+use crate::EguiWrapper;
+
+/// The size of the animation preview, in points.
+const PREVIEW_SIZE: f32 = 128.0;
+
+/// A widget for slicing a sprite sheet into a grid of frames and
+/// previewing an animation made from some of those frames.
+///
+/// The widget has controls for the size of each cell, the padding between
+/// cells, the offset of the first cell from the top-left corner of the
+/// texture, and the range of frames and frame time used for the animation.
+/// Frames are numbered left to right, top to bottom. Clicking a cell on the
+/// sheet makes it the first frame of the animation, and shift-clicking
+/// makes it the last frame (or the first frame, if it comes before the
+/// current first frame).
+///
+/// The animation's frames can be read with [`frames`](SpriteSheetSlicer::frames),
+/// turned into an [`Animation`] with [`animation`](SpriteSheetSlicer::animation),
+/// or copied to the clipboard as Rust code from the widget.
+pub struct SpriteSheetSlicer {
+	texture_id: egui::TextureId,
+	texture_width: i32,
+	texture_height: i32,
+	cell_size: (i32, i32),
+	padding: (i32, i32),
+	offset: (i32, i32),
+	first_frame: usize,
+	last_frame: usize,
+	frame_time: Duration,
+	id_source: egui::Id,
+}
+
+impl SpriteSheetSlicer {
+	/// Creates a new [`SpriteSheetSlicer`] for the given texture,
+	/// registering the texture with the [`EguiWrapper`].
+	pub fn new(egui: &mut EguiWrapper, texture: &Texture) -> Self {
+		Self::from_texture_id(
+			egui.register_texture(texture.clone()),
+			texture.width(),
+			texture.height(),
+		)
+	}
+
+	/// Creates a new [`SpriteSheetSlicer`] for a texture that has already
+	/// been registered with an [`EguiWrapper`].
+	pub fn from_texture_id(texture_id: egui::TextureId, width: i32, height: i32) -> Self {
+		Self {
+			texture_id,
+			texture_width: width,
+			texture_height: height,
+			cell_size: (width.clamp(1, 16), height.clamp(1, 16)),
+			padding: (0, 0),
+			offset: (0, 0),
+			first_frame: 0,
+			last_frame: 0,
+			frame_time: Duration::from_millis(100),
+			id_source: egui::Id::new("egui_tetra_sprite_sheet_slicer"),
+		}
+	}
+
+	/// Sets the size of each cell in the grid and returns the slicer.
+	pub fn cell_size(mut self, width: i32, height: i32) -> Self {
+		self.cell_size = (width.max(1), height.max(1));
+		self
+	}
+
+	/// Sets the space between cells and returns the slicer.
+	pub fn padding(mut self, x: i32, y: i32) -> Self {
+		self.padding = (x.max(0), y.max(0));
+		self
+	}
+
+	/// Sets the position of the first cell and returns the slicer.
+	pub fn offset(mut self, x: i32, y: i32) -> Self {
+		self.offset = (x.max(0), y.max(0));
+		self
+	}
+
+	/// Sets the range of frames used for the animation and returns
+	/// the slicer.
+	///
+	/// If the range is backwards, its ends are swapped.
+	pub fn frame_range(mut self, frames: RangeInclusive<usize>) -> Self {
+		let (start, end) = frames.into_inner();
+		self.first_frame = start.min(end);
+		self.last_frame = start.max(end);
+		self
+	}
+
+	/// Sets how long each frame of the animation is shown and returns
+	/// the slicer.
+	pub fn frame_time(mut self, frame_time: Duration) -> Self {
+		self.frame_time = frame_time;
+		self
+	}
+
+	/// Sets a source for the widget's ID and returns the slicer.
+	///
+	/// This is needed when showing more than one [`SpriteSheetSlicer`]
+	/// in the same [`Ui`](egui::Ui).
+	pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+		self.id_source = egui::Id::new(id_source);
+		self
+	}
+
+	/// Returns the number of columns and rows of cells that fit on
+	/// the texture.
+	pub fn grid_size(&self) -> (usize, usize) {
+		let count = |texture_size: i32, offset: i32, cell_size: i32, padding: i32| {
+			((texture_size - offset + padding) / (cell_size + padding)).max(0) as usize
+		};
+		(
+			count(
+				self.texture_width,
+				self.offset.0,
+				self.cell_size.0,
+				self.padding.0,
+			),
+			count(
+				self.texture_height,
+				self.offset.1,
+				self.cell_size.1,
+				self.padding.1,
+			),
+		)
+	}
+
+	/// Returns the region of the texture covered by the cell with
+	/// the given frame index.
+	pub fn cell(&self, index: usize) -> Rectangle {
+		let columns = self.grid_size().0.max(1);
+		let (column, row) = ((index % columns) as i32, (index / columns) as i32);
+		Rectangle::new(
+			(self.offset.0 + column * (self.cell_size.0 + self.padding.0)) as f32,
+			(self.offset.1 + row * (self.cell_size.1 + self.padding.1)) as f32,
+			self.cell_size.0 as f32,
+			self.cell_size.1 as f32,
+		)
+	}
+
+	/// Returns the regions of the texture used for each frame of the
+	/// animation.
+	///
+	/// This is empty if none of the frames fit on the texture.
+	pub fn frames(&self) -> Vec<Rectangle> {
+		let (columns, rows) = self.grid_size();
+		let last_frame = self.last_frame.min((columns * rows).saturating_sub(1));
+		(self.first_frame..=last_frame)
+			.map(|index| self.cell(index))
+			.collect()
+	}
+
+	/// Creates a looping [`Animation`] from the frames and frame time,
+	/// or returns `None` if none of the frames fit on the texture.
+	///
+	/// The texture should be the same one the slicer is showing.
+	pub fn animation(&self, texture: Texture) -> Option<Animation> {
+		let frames = self.frames();
+		if frames.is_empty() {
+			// tetra's animations can't have zero frames
+			return None;
+		}
+		Some(Animation::new(texture, frames, self.frame_time))
+	}
+
+	/// Shows the sprite sheet, the settings, and the animation preview.
+	///
+	/// The response is marked as [changed](egui::Response::changed) when
+	/// any of the settings change.
+	pub fn ui(&mut self, ui: &mut egui::Ui) -> egui::Response {
+		let mut changed = false;
+		let mut response = ui
+			.horizontal_top(|ui| {
+				ui.vertical(|ui| {
+					changed |= self.settings_ui(ui);
+					ui.separator();
+					self.preview_ui(ui);
+				});
+				changed |= self.sheet_ui(ui);
+			})
+			.response;
+		if changed {
+			response.mark_changed();
+		}
+		response
+	}
+
+	fn settings_ui(&mut self, ui: &mut egui::Ui) -> bool {
+		let mut changed = false;
+		egui::Grid::new(self.id_source.with("settings")).show(ui, |ui| {
+			let mut pair = |ui: &mut egui::Ui, label: &str, value: &mut (i32, i32), min: i32| {
+				ui.label(label);
+				changed |= ui
+					.add(egui::DragValue::new(&mut value.0).clamp_range(min..=i32::MAX))
+					.changed();
+				changed |= ui
+					.add(egui::DragValue::new(&mut value.1).clamp_range(min..=i32::MAX))
+					.changed();
+				ui.end_row();
+			};
+			pair(ui, "Cell size", &mut self.cell_size, 1);
+			pair(ui, "Padding", &mut self.padding, 0);
+			pair(ui, "Offset", &mut self.offset, 0);
+			let (columns, rows) = self.grid_size();
+			let max_frame = (columns * rows).saturating_sub(1);
+			ui.label("Frames");
+			if ui
+				.add(egui::DragValue::new(&mut self.first_frame).clamp_range(0..=max_frame))
+				.changed()
+			{
+				self.last_frame = self.last_frame.max(self.first_frame);
+				changed = true;
+			}
+			changed |= ui
+				.add(
+					egui::DragValue::new(&mut self.last_frame)
+						.clamp_range(self.first_frame..=max_frame.max(self.first_frame)),
+				)
+				.changed();
+			ui.end_row();
+			ui.label("Frame time");
+			let mut frame_time_ms = self.frame_time.as_secs_f64() * 1000.0;
+			if ui
+				.add(
+					egui::DragValue::new(&mut frame_time_ms)
+						.clamp_range(1.0..=10000.0)
+						.suffix(" ms"),
+				)
+				.changed()
+			{
+				self.frame_time = Duration::from_secs_f64(frame_time_ms / 1000.0);
+				changed = true;
+			}
+			ui.end_row();
+		});
+		if ui.button("Copy frames").clicked() {
+			let frames: Vec<String> = self
+				.frames()
+				.iter()
+				.map(|frame| {
+					format!(
+						"Rectangle::new({:.1}, {:.1}, {:.1}, {:.1})",
+						frame.x, frame.y, frame.width, frame.height
+					)
+				})
+				.collect();
+			ui.output().copied_text = format!("vec![{}]", frames.join(", "));
+		}
+		changed
+	}
+
+	fn preview_ui(&self, ui: &mut egui::Ui) {
+		let frames = self.frames();
+		let (rect, _) =
+			ui.allocate_exact_size(egui::Vec2::splat(PREVIEW_SIZE), egui::Sense::hover());
+		ui.painter()
+			.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+		if frames.is_empty() {
+			return;
+		}
+		let frame_seconds = self.frame_time.as_secs_f64().max(f64::EPSILON);
+		let index = (ui.input().time / frame_seconds) as usize % frames.len();
+		let frame = frames[index];
+		let scale = (PREVIEW_SIZE / frame.width).min(PREVIEW_SIZE / frame.height);
+		let uv = egui::Rect::from_min_max(
+			egui::pos2(
+				frame.x / self.texture_width as f32,
+				frame.y / self.texture_height as f32,
+			),
+			egui::pos2(
+				(frame.x + frame.width) / self.texture_width as f32,
+				(frame.y + frame.height) / self.texture_height as f32,
+			),
+		);
+		egui::Image::new(
+			self.texture_id,
+			egui::vec2(frame.width, frame.height) * scale,
+		)
+		.uv(uv)
+		.paint_at(
+			ui,
+			egui::Rect::from_center_size(
+				rect.center(),
+				egui::vec2(frame.width, frame.height) * scale,
+			),
+		);
+		ui.label(format!("Frame {} of {}", index + 1, frames.len()));
+		ui.ctx().request_repaint();
+	}
+
+	fn sheet_ui(&mut self, ui: &mut egui::Ui) -> bool {
+		let texture_size = egui::vec2(self.texture_width as f32, self.texture_height as f32);
+		let available = ui.available_size();
+		let scale = (available.x / texture_size.x)
+			.min(available.y / texture_size.y)
+			.max(f32::EPSILON);
+		let (rect, response) = ui.allocate_exact_size(texture_size * scale, egui::Sense::click());
+		let painter = ui.painter_at(rect);
+		painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+		egui::Image::new(self.texture_id, rect.size()).paint_at(ui, rect);
+
+		let cell_rect = |index: usize| {
+			let cell = self.cell(index);
+			egui::Rect::from_min_size(
+				rect.min + egui::vec2(cell.x, cell.y) * scale,
+				egui::vec2(cell.width, cell.height) * scale,
+			)
+		};
+		let (columns, rows) = self.grid_size();
+		let animation_frames = self.first_frame..=self.last_frame;
+		for index in 0..columns * rows {
+			let cell_rect = cell_rect(index);
+			let (stroke, text_color) = if animation_frames.contains(&index) {
+				(
+					ui.visuals().selection.stroke,
+					ui.visuals().selection.stroke.color,
+				)
+			} else {
+				(
+					egui::Stroke::new(1.0, egui::Color32::from_white_alpha(64)),
+					egui::Color32::from_white_alpha(128),
+				)
+			};
+			painter.rect_stroke(cell_rect, 0.0, stroke);
+			painter.text(
+				cell_rect.left_top() + egui::vec2(2.0, 1.0),
+				egui::Align2::LEFT_TOP,
+				index,
+				egui::TextStyle::Small,
+				text_color,
+			);
+		}
+
+		let clicked_cell = response
+			.interact_pointer_pos()
+			.filter(|_| response.clicked())
+			.and_then(|pos| (0..columns * rows).find(|index| cell_rect(*index).contains(pos)));
+		match clicked_cell {
+			Some(index) => {
+				if ui.input().modifiers.shift {
+					if index < self.first_frame {
+						self.first_frame = index;
+					} else {
+						self.last_frame = index;
+					}
+				} else {
+					self.first_frame = index;
+					self.last_frame = self.last_frame.max(index);
+				}
+				true
+			}
+			None => false,
+		}
+	}
+}
@@ -0,0 +1,265 @@
+use tetra::graphics::{Rectangle, Texture};
+
+use crate::EguiWrapper;
+
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 64.0;
+/// How many screen pixels the mouse wheel needs to scroll to double
+/// the zoom level.
+const ZOOM_SCROLL_DISTANCE: f32 = 200.0;
+/// The zoom level at which texels start being drawn individually.
+const NEAREST_ZOOM: f32 = 2.0;
+/// The most texels that will be drawn individually in one frame.
+const MAX_TEXEL_QUADS: i32 = 16384;
+/// The zoom level at which the pixel grid starts being drawn.
+const GRID_ZOOM: f32 = 6.0;
+
+/// A widget for inspecting a [`Texture`] (or the texture of a
+/// [`Canvas`](tetra::graphics::Canvas)) in the GUI.
+///
+/// - Scroll to zoom in and out, and drag with the right or middle mouse
+///   button to pan.
+/// - When zoomed in, texels are drawn as sharp squares regardless of the
+///   texture's filter mode, and a pixel grid is shown.
+/// - The texel under the mouse is shown in the corner of the widget.
+/// - Drag with the left mouse button to select a region of the texture,
+///   such as the bounds of a sprite. The response is marked as
+///   [changed](egui::Response::changed) when a selection is finished.
+///
+/// ```
+/// # fn ui(ui: &mut egui_tetra::egui::Ui, viewer: &mut egui_tetra::TextureViewer) {
+/// if viewer.ui(ui).changed() {
+/// 	println!("selected {:?}", viewer.selection());
+/// }
+/// # }
+/// ```
+pub struct TextureViewer {
+	texture_id: egui::TextureId,
+	texture_size: egui::Vec2,
+	show_grid: bool,
+	/// The zoom level, or `None` if the texture should be fit to
+	/// the widget the next time it's shown.
+	zoom: Option<f32>,
+	/// The position of the texture's top-left corner relative to
+	/// the widget's top-left corner.
+	pan: egui::Vec2,
+	selection: Option<Rectangle<i32>>,
+	selection_start: Option<(i32, i32)>,
+}
+
+impl TextureViewer {
+	/// Creates a new [`TextureViewer`] showing the given texture,
+	/// registering the texture with the [`EguiWrapper`].
+	pub fn new(egui: &mut EguiWrapper, texture: &Texture) -> Self {
+		Self::from_texture_id(
+			egui.register_texture(texture.clone()),
+			texture.width(),
+			texture.height(),
+		)
+	}
+
+	/// Creates a new [`TextureViewer`] showing a texture that has already
+	/// been registered with an [`EguiWrapper`].
+	pub fn from_texture_id(texture_id: egui::TextureId, width: i32, height: i32) -> Self {
+		Self {
+			texture_id,
+			texture_size: egui::vec2(width as f32, height as f32),
+			show_grid: true,
+			zoom: None,
+			pan: egui::Vec2::ZERO,
+			selection: None,
+			selection_start: None,
+		}
+	}
+
+	/// Sets whether a pixel grid is drawn when zoomed in and returns
+	/// the viewer.
+	///
+	/// Defaults to `true`.
+	pub fn show_grid(mut self, show_grid: bool) -> Self {
+		self.show_grid = show_grid;
+		self
+	}
+
+	/// Returns the ID of the texture in the egui context.
+	pub fn texture_id(&self) -> egui::TextureId {
+		self.texture_id
+	}
+
+	/// Returns the selected region of the texture, in texels.
+	pub fn selection(&self) -> Option<Rectangle<i32>> {
+		self.selection
+	}
+
+	/// Sets the selected region of the texture, in texels.
+	pub fn set_selection(&mut self, selection: Option<Rectangle<i32>>) {
+		self.selection = selection;
+	}
+
+	/// Fits the texture to the widget the next time it's shown.
+	pub fn reset_view(&mut self) {
+		self.zoom = None;
+	}
+
+	/// Shows the texture, filling the available space.
+	pub fn ui(&mut self, ui: &mut egui::Ui) -> egui::Response {
+		let (rect, mut response) =
+			ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+		let mut zoom = match self.zoom {
+			Some(zoom) => zoom,
+			None => {
+				let zoom = (rect.width() / self.texture_size.x)
+					.min(rect.height() / self.texture_size.y)
+					.clamp(MIN_ZOOM, MAX_ZOOM);
+				self.pan = (rect.size() - self.texture_size * zoom) / 2.0;
+				zoom
+			}
+		};
+
+		// zoom around the mouse
+		if let Some(hover_pos) = response.hover_pos() {
+			let scroll = ui.input().scroll_delta.y;
+			if scroll != 0.0 {
+				let new_zoom =
+					(zoom * (scroll / ZOOM_SCROLL_DISTANCE).exp2()).clamp(MIN_ZOOM, MAX_ZOOM);
+				let anchor = hover_pos - rect.min;
+				self.pan = anchor - (anchor - self.pan) * (new_zoom / zoom);
+				zoom = new_zoom;
+			}
+		}
+		self.zoom = Some(zoom);
+		if response.dragged_by(egui::PointerButton::Secondary)
+			|| response.dragged_by(egui::PointerButton::Middle)
+		{
+			self.pan += response.drag_delta();
+		}
+
+		let image_rect = egui::Rect::from_min_size(rect.min + self.pan, self.texture_size * zoom);
+		let texel_at = |pos: egui::Pos2| {
+			let texel = (pos - image_rect.min) / zoom;
+			(texel.x.floor() as i32, texel.y.floor() as i32)
+		};
+		let texture_size = self.texture_size;
+		let clamp_texel = |(x, y): (i32, i32)| {
+			(
+				x.clamp(0, texture_size.x as i32 - 1),
+				y.clamp(0, texture_size.y as i32 - 1),
+			)
+		};
+
+		// select a region by dragging
+		if response.dragged_by(egui::PointerButton::Primary) {
+			if let (None, Some(press_origin)) =
+				(self.selection_start, ui.input().pointer.press_origin())
+			{
+				self.selection_start = Some(clamp_texel(texel_at(press_origin)));
+			}
+			if let (Some(start), Some(pointer_pos)) =
+				(self.selection_start, ui.input().pointer.interact_pos())
+			{
+				let end = clamp_texel(texel_at(pointer_pos));
+				let (min_x, max_x) = (start.0.min(end.0), start.0.max(end.0));
+				let (min_y, max_y) = (start.1.min(end.1), start.1.max(end.1));
+				self.selection = Some(Rectangle::new(
+					min_x,
+					min_y,
+					max_x - min_x + 1,
+					max_y - min_y + 1,
+				));
+			}
+		} else if self.selection_start.take().is_some() {
+			response.mark_changed();
+		}
+
+		let painter = ui.painter_at(rect);
+		painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+		let visible = rect.intersect(image_rect);
+		let (min_x, min_y) = clamp_texel(texel_at(visible.min));
+		let (max_x, max_y) = clamp_texel(texel_at(visible.max));
+		let texel_rect = |x: i32, y: i32| {
+			egui::Rect::from_min_size(
+				image_rect.min + egui::vec2(x as f32, y as f32) * zoom,
+				egui::Vec2::splat(zoom),
+			)
+		};
+		let visible_texels = (max_x - min_x + 1) * (max_y - min_y + 1);
+		if zoom >= NEAREST_ZOOM && visible.is_positive() && visible_texels <= MAX_TEXEL_QUADS {
+			// sampling each texel at its center gives the same result as
+			// nearest neighbor filtering, even if the texture uses linear
+			// filtering
+			let mut mesh = egui::epaint::Mesh::with_texture(self.texture_id);
+			for y in min_y..=max_y {
+				for x in min_x..=max_x {
+					let uv = egui::pos2(
+						(x as f32 + 0.5) / self.texture_size.x,
+						(y as f32 + 0.5) / self.texture_size.y,
+					);
+					mesh.add_rect_with_uv(
+						texel_rect(x, y),
+						egui::Rect::from_min_max(uv, uv),
+						egui::Color32::WHITE,
+					);
+				}
+			}
+			painter.add(egui::Shape::mesh(mesh));
+		} else {
+			let mut mesh = egui::epaint::Mesh::with_texture(self.texture_id);
+			mesh.add_rect_with_uv(
+				image_rect,
+				egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+				egui::Color32::WHITE,
+			);
+			painter.add(egui::Shape::mesh(mesh));
+		}
+
+		if self.show_grid && zoom >= GRID_ZOOM && visible.is_positive() {
+			let stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(32));
+			for x in min_x..=max_x + 1 {
+				let x = image_rect.min.x + x as f32 * zoom;
+				painter.line_segment(
+					[egui::pos2(x, visible.min.y), egui::pos2(x, visible.max.y)],
+					stroke,
+				);
+			}
+			for y in min_y..=max_y + 1 {
+				let y = image_rect.min.y + y as f32 * zoom;
+				painter.line_segment(
+					[egui::pos2(visible.min.x, y), egui::pos2(visible.max.x, y)],
+					stroke,
+				);
+			}
+		}
+
+		if let Some(selection) = self.selection {
+			painter.rect_stroke(
+				texel_rect(selection.x, selection.y).union(texel_rect(
+					selection.x + selection.width - 1,
+					selection.y + selection.height - 1,
+				)),
+				0.0,
+				ui.visuals().selection.stroke,
+			);
+		}
+
+		let mut info = vec![format!("{:.0}%", zoom * 100.0)];
+		if let Some(hover_pos) = response.hover_pos().filter(|pos| image_rect.contains(*pos)) {
+			let (x, y) = texel_at(hover_pos);
+			info.push(format!("({}, {})", x, y));
+		}
+		if let Some(selection) = self.selection {
+			info.push(format!(
+				"selection: {}, {} {}x{}",
+				selection.x, selection.y, selection.width, selection.height
+			));
+		}
+		painter.text(
+			rect.left_bottom() + egui::vec2(4.0, -4.0),
+			egui::Align2::LEFT_BOTTOM,
+			info.join("  "),
+			egui::TextStyle::Monospace,
+			ui.visuals().strong_text_color(),
+		);
+
+		response
+	}
+}
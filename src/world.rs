@@ -0,0 +1,141 @@
+use tetra::math::{Mat4, Vec2, Vec3};
+
+use crate::{convert::FromTetra, EguiWrapper};
+
+/// Converts a position in world coordinates to a position in screen
+/// coordinates using a transform matrix, such as one returned by
+/// [`Camera::as_matrix`](tetra::graphics::Camera::as_matrix).
+pub fn world_to_screen(transform: Mat4<f32>, world_position: Vec2<f32>) -> Vec2<f32> {
+	transform.mul_point(Vec3::from(world_position)).xy()
+}
+
+/// Converts a position in screen coordinates to a position in world
+/// coordinates using a transform matrix, such as one returned by
+/// [`Camera::as_matrix`](tetra::graphics::Camera::as_matrix).
+pub fn screen_to_world(transform: Mat4<f32>, screen_position: Vec2<f32>) -> Vec2<f32> {
+	world_to_screen(transform.inverted(), screen_position)
+}
+
+/// An [`egui::Area`] that follows a position in the game world,
+/// such as a nameplate above a character.
+///
+/// The area is positioned using a transform matrix (usually from a
+/// [`Camera`](tetra::graphics::Camera)), so it follows the camera as it
+/// pans and zooms. Areas that would be entirely off-screen are not shown.
+///
+/// ```
+/// # fn ui(egui_ctx: &egui_tetra::egui::CtxRef, camera: &tetra::graphics::Camera) {
+/// use egui_tetra::{egui, WorldArea};
+/// use tetra::math::Vec2;
+///
+/// WorldArea::new("player_nameplate", Vec2::new(100.0, 50.0))
+/// 	.pivot(egui::Align2::CENTER_BOTTOM)
+/// 	.show(egui_ctx, camera.as_matrix(), |ui| {
+/// 		ui.label("Player");
+/// 	});
+/// # }
+/// ```
+pub struct WorldArea {
+	id: egui::Id,
+	world_position: Vec2<f32>,
+	pivot: egui::Align2,
+	offset: egui::Vec2,
+	order: egui::Order,
+	interactable: bool,
+}
+
+impl WorldArea {
+	/// Creates a new [`WorldArea`] anchored to a position in world
+	/// coordinates.
+	pub fn new(id: impl std::hash::Hash, world_position: Vec2<f32>) -> Self {
+		Self {
+			id: egui::Id::new(id),
+			world_position,
+			pivot: egui::Align2::LEFT_TOP,
+			offset: egui::Vec2::ZERO,
+			order: egui::Order::Background,
+			interactable: true,
+		}
+	}
+
+	/// Sets which point of the area is placed at the world position.
+	///
+	/// Defaults to the top-left corner.
+	pub fn pivot(mut self, pivot: egui::Align2) -> Self {
+		self.pivot = pivot;
+		self
+	}
+
+	/// Sets an offset in screen coordinates to apply after converting
+	/// the world position to screen coordinates.
+	pub fn offset(mut self, offset: impl Into<egui::Vec2>) -> Self {
+		self.offset = offset.into();
+		self
+	}
+
+	/// Sets the layer [`Order`](egui::Order) of the area.
+	///
+	/// Defaults to [`Order::Background`](egui::Order::Background),
+	/// so world areas are drawn behind windows.
+	pub fn order(mut self, order: egui::Order) -> Self {
+		self.order = order;
+		self
+	}
+
+	/// Sets whether the area can be interacted with.
+	///
+	/// Defaults to `true`.
+	pub fn interactable(mut self, interactable: bool) -> Self {
+		self.interactable = interactable;
+		self
+	}
+
+	/// Shows the area, or returns `None` if the area is off-screen.
+	pub fn show<R>(
+		self,
+		egui_ctx: &egui::CtxRef,
+		transform: Mat4<f32>,
+		add_contents: impl FnOnce(&mut egui::Ui) -> R,
+	) -> Option<egui::InnerResponse<R>> {
+		// egui doesn't let us read an area's size, so we remember
+		// the size from the previous frame to apply the pivot.
+		let size_id = self.id.with("egui_tetra_world_area_size");
+		let size = egui_ctx
+			.memory()
+			.data
+			.get_temp::<egui::Vec2>(size_id)
+			.unwrap_or_default();
+		let anchor =
+			egui::Pos2::from_tetra(world_to_screen(transform, self.world_position)) + self.offset;
+		let rect = self
+			.pivot
+			.anchor_rect(egui::Rect::from_min_size(anchor, size));
+		if !egui_ctx.input().screen_rect().intersects(rect) {
+			return None;
+		}
+		let response = egui::Area::new(self.id)
+			.order(self.order)
+			.interactable(self.interactable)
+			.fixed_pos(rect.min)
+			.show(egui_ctx, add_contents);
+		egui_ctx
+			.memory()
+			.data
+			.insert_temp(size_id, response.response.rect.size());
+		Some(response)
+	}
+}
+
+impl EguiWrapper {
+	/// Shows a [`WorldArea`] positioned using a transform matrix (usually from a
+	/// [`Camera`](tetra::graphics::Camera)), or returns `None` if the area is
+	/// off-screen.
+	pub fn show_world_area<R>(
+		&self,
+		area: WorldArea,
+		transform: Mat4<f32>,
+		add_contents: impl FnOnce(&mut egui::Ui) -> R,
+	) -> Option<egui::InnerResponse<R>> {
+		area.show(self.ctx(), transform, add_contents)
+	}
+}
@@ -0,0 +1,164 @@
+use egui_tetra::{
+	convert::{FromEgui, FromTetra, IntoEgui, IntoTetra},
+	egui,
+};
+use tetra::{
+	graphics::{Color, Rectangle},
+	input::{Key, MouseButton},
+	math::Vec2,
+};
+
+const EGUI_KEYS: [egui::Key; 51] = [
+	egui::Key::ArrowDown,
+	egui::Key::ArrowLeft,
+	egui::Key::ArrowRight,
+	egui::Key::ArrowUp,
+	egui::Key::Escape,
+	egui::Key::Tab,
+	egui::Key::Backspace,
+	egui::Key::Enter,
+	egui::Key::Space,
+	egui::Key::Insert,
+	egui::Key::Delete,
+	egui::Key::Home,
+	egui::Key::End,
+	egui::Key::PageUp,
+	egui::Key::PageDown,
+	egui::Key::Num0,
+	egui::Key::Num1,
+	egui::Key::Num2,
+	egui::Key::Num3,
+	egui::Key::Num4,
+	egui::Key::Num5,
+	egui::Key::Num6,
+	egui::Key::Num7,
+	egui::Key::Num8,
+	egui::Key::Num9,
+	egui::Key::A,
+	egui::Key::B,
+	egui::Key::C,
+	egui::Key::D,
+	egui::Key::E,
+	egui::Key::F,
+	egui::Key::G,
+	egui::Key::H,
+	egui::Key::I,
+	egui::Key::J,
+	egui::Key::K,
+	egui::Key::L,
+	egui::Key::M,
+	egui::Key::N,
+	egui::Key::O,
+	egui::Key::P,
+	egui::Key::Q,
+	egui::Key::R,
+	egui::Key::S,
+	egui::Key::T,
+	egui::Key::U,
+	egui::Key::V,
+	egui::Key::W,
+	egui::Key::X,
+	egui::Key::Y,
+	egui::Key::Z,
+];
+
+fn assert_colors_close(a: Color, b: Color) {
+	let tolerance = 1.0 / 255.0;
+	assert!(
+		(a.r - b.r).abs() <= tolerance
+			&& (a.g - b.g).abs() <= tolerance
+			&& (a.b - b.b).abs() <= tolerance
+			&& (a.a - b.a).abs() <= tolerance,
+		"{:?} != {:?}",
+		a,
+		b
+	);
+}
+
+#[test]
+fn vec2_round_trip() {
+	let vec2 = Vec2::new(12.5, -3.25);
+	let pos2: egui::Pos2 = vec2.into_egui();
+	assert_eq!(pos2, egui::pos2(12.5, -3.25));
+	assert_eq!(Vec2::from_egui(pos2), vec2);
+	let egui_vec2: egui::Vec2 = vec2.into_egui();
+	assert_eq!(egui_vec2, egui::vec2(12.5, -3.25));
+	assert_eq!(Vec2::from_egui(egui_vec2), vec2);
+}
+
+#[test]
+fn rectangle_round_trip() {
+	let rectangle = Rectangle::new(10.5, 20.0, 30.25, 40.0);
+	let rect: egui::Rect = rectangle.into_egui();
+	assert_eq!(rect.min, egui::pos2(10.5, 20.0));
+	assert_eq!(rect.max, egui::pos2(40.75, 60.0));
+	assert_eq!(Rectangle::<f32>::from_egui(rect), rectangle);
+
+	let rectangle = Rectangle::new(-5, 8, 100, 50);
+	let rect = egui::Rect::from_tetra(rectangle);
+	assert_eq!(rect.min, egui::pos2(-5.0, 8.0));
+	assert_eq!(rect.max, egui::pos2(95.0, 58.0));
+	assert_eq!(Rectangle::<i32>::from_egui(rect), rectangle);
+}
+
+#[test]
+fn color32_round_trip() {
+	for color in [
+		Color::rgba8(0, 0, 0, 255),
+		Color::rgba8(255, 255, 255, 255),
+		Color::rgba8(100, 149, 237, 255),
+		Color::rgba8(255, 128, 0, 128),
+	] {
+		let color32: egui::Color32 = color.into_egui();
+		assert_colors_close(Color::from_egui(color32), color);
+	}
+	let color32 = egui::Color32::from_rgb(100, 149, 237);
+	assert_eq!(egui::Color32::from_tetra(color32.into_tetra()), color32);
+}
+
+#[test]
+fn rgba_round_trip() {
+	for color in [
+		Color::BLACK,
+		Color::WHITE,
+		Color::rgba(0.25, 0.5, 0.75, 1.0),
+		Color::rgba(1.0, 0.5, 0.0, 0.5),
+	] {
+		let rgba: egui::Rgba = color.into_egui();
+		assert_colors_close(Color::from_egui(rgba), color);
+	}
+	let rgba: egui::Rgba = Color::rgba(0.5, 0.5, 0.5, 1.0).into_egui();
+	assert!((rgba.r() - 0.214).abs() < 0.001, "Rgba should be linear");
+}
+
+#[test]
+fn key_round_trip() {
+	for key in EGUI_KEYS {
+		let tetra_key: Key = key.into_tetra();
+		assert_eq!(Option::<egui::Key>::from_tetra(tetra_key), Some(key));
+	}
+	assert_eq!(
+		Option::<egui::Key>::from_tetra(Key::NumPad5),
+		Some(egui::Key::Num5)
+	);
+	assert_eq!(Option::<egui::Key>::from_tetra(Key::F1), None);
+}
+
+#[test]
+fn mouse_button_round_trip() {
+	for button in [
+		egui::PointerButton::Primary,
+		egui::PointerButton::Secondary,
+		egui::PointerButton::Middle,
+	] {
+		let tetra_button: MouseButton = button.into_tetra();
+		assert_eq!(
+			Option::<egui::PointerButton>::from_tetra(tetra_button),
+			Some(button)
+		);
+	}
+	assert_eq!(
+		Option::<egui::PointerButton>::from_tetra(MouseButton::X1),
+		None
+	);
+}